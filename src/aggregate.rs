@@ -0,0 +1,264 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{borrow::Borrow, iter::Sum};
+
+use fpdec::{Decimal, DivRounded};
+
+use crate::{AmountT, Currency, Money, MoneyError, Quantity};
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns the sum of the money amounts in `iter`, which must all have
+    /// the same `Currency` unit.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Money, EUR, USD};
+    /// let payments = [Dec!(17.5) * EUR, Dec!(4.25) * EUR];
+    /// assert_eq!(Money::try_sum(&payments), Ok(Dec!(21.75) * EUR));
+    /// let payments = [Dec!(17.5) * EUR, Dec!(4.25) * USD];
+    /// assert!(Money::try_sum(&payments).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if
+    /// * `iter` is empty (`MoneyError::Empty`),
+    /// * the items in `iter` have different `Currency` units
+    ///   (`MoneyError::CurrencyMismatch`).
+    pub fn try_sum<I, T>(iter: I) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Borrow<Self>,
+    {
+        let mut iter = iter.into_iter();
+        let first = *iter.next().ok_or(MoneyError::Empty)?.borrow();
+        Self::sum_in(first.unit, iter).map(|sum| sum + first)
+    }
+
+    /// Returns the sum of the money amounts in `iter`, which must all have
+    /// the `Currency` unit `currency`.
+    ///
+    /// If `iter` is empty, zero in `currency` is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::CurrencyMismatch)` if an item in `iter` has
+    /// a `Currency` unit different from `currency`.
+    pub fn sum_in<I, T>(
+        currency: Currency,
+        iter: I,
+    ) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Borrow<Self>,
+    {
+        let mut amount = AmountT::ZERO;
+        for item in iter {
+            let item = item.borrow();
+            check_currency(currency, item)?;
+            amount += item.amount;
+        }
+        Ok(Self::new(amount, currency))
+    }
+
+    /// Returns the smallest of the money amounts in `iter`, which must all
+    /// have the `Currency` unit `currency`, or `None` if `iter` is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::CurrencyMismatch)` if an item in `iter` has
+    /// a `Currency` unit different from `currency`.
+    pub fn min_in<I, T>(
+        currency: Currency,
+        iter: I,
+    ) -> Result<Option<Self>, MoneyError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Borrow<Self>,
+    {
+        fold_in(currency, iter, |acc, item| {
+            if item.amount < acc.amount {
+                item
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Returns the largest of the money amounts in `iter`, which must all
+    /// have the `Currency` unit `currency`, or `None` if `iter` is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::CurrencyMismatch)` if an item in `iter` has
+    /// a `Currency` unit different from `currency`.
+    pub fn max_in<I, T>(
+        currency: Currency,
+        iter: I,
+    ) -> Result<Option<Self>, MoneyError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Borrow<Self>,
+    {
+        fold_in(currency, iter, |acc, item| {
+            if item.amount > acc.amount {
+                item
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Returns the arithmetic mean of the money amounts in `iter`, which
+    /// must all have the `Currency` unit `currency`, or `None` if `iter` is
+    /// empty.
+    ///
+    /// The result is rounded to the minor unit of `currency`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Money, EUR};
+    /// let payments = [Dec!(10) * EUR, Dec!(10) * EUR, Dec!(10.01) * EUR];
+    /// assert_eq!(
+    ///     Money::average_in(EUR, &payments),
+    ///     Ok(Some(Dec!(10) * EUR))
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::CurrencyMismatch)` if an item in `iter` has
+    /// a `Currency` unit different from `currency`.
+    pub fn average_in<I, T>(
+        currency: Currency,
+        iter: I,
+    ) -> Result<Option<Self>, MoneyError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Borrow<Self>,
+    {
+        let mut amount = AmountT::ZERO;
+        let mut count = 0_u64;
+        for item in iter {
+            let item = item.borrow();
+            check_currency(currency, item)?;
+            amount += item.amount;
+            count += 1;
+        }
+        Ok((count > 0).then(|| {
+            Self::new(
+                amount
+                    .div_rounded(Decimal::from(count), currency.minor_unit()),
+                currency,
+            )
+        }))
+    }
+}
+
+#[inline]
+pub(crate) fn check_currency(
+    currency: Currency,
+    item: &Money,
+) -> Result<(), MoneyError> {
+    if item.unit == currency {
+        Ok(())
+    } else {
+        Err(MoneyError::CurrencyMismatch {
+            expected: currency,
+            found: item.unit,
+        })
+    }
+}
+
+fn fold_in<I, T, F>(
+    currency: Currency,
+    iter: I,
+    f: F,
+) -> Result<Option<Money>, MoneyError>
+where
+    I: IntoIterator<Item = T>,
+    T: Borrow<Money>,
+    F: Fn(Money, Money) -> Money,
+{
+    let mut res: Option<Money> = None;
+    for item in iter {
+        let item = *item.borrow();
+        check_currency(currency, &item)?;
+        res = Some(res.map_or(item, |acc| f(acc, item)));
+    }
+    Ok(res)
+}
+
+impl Sum<Money> for Option<Money> {
+    /// Returns the sum of the money amounts in `iter`, or `None` if `iter`
+    /// is empty or its items have different `Currency` units.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Money, EUR, USD};
+    /// let payments = vec![Dec!(17.5) * EUR, Dec!(4.25) * EUR];
+    /// let total: Option<Money> = payments.into_iter().sum();
+    /// assert_eq!(total, Some(Dec!(21.75) * EUR));
+    /// let total: Option<Money> = Vec::<Money>::new().into_iter().sum();
+    /// assert_eq!(total, None);
+    /// ```
+    #[inline]
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Self {
+        Money::try_sum(iter).ok()
+    }
+}
+
+impl<'a> Sum<&'a Money> for Option<Money> {
+    /// Returns the sum of the money amounts in `iter`, or `None` if `iter`
+    /// is empty or its items have different `Currency` units.
+    #[inline]
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Self {
+        Money::try_sum(iter).ok()
+    }
+}
+
+impl Sum<Money> for Result<Money, MoneyError> {
+    /// Returns the sum of the money amounts in `iter`.
+    ///
+    /// See `Money::try_sum` for the errors returned.
+    #[inline]
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Self {
+        Money::try_sum(iter)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Result<Money, MoneyError> {
+    /// Returns the sum of the money amounts in `iter`.
+    ///
+    /// See `Money::try_sum` for the errors returned.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Money, MoneyError, EUR, USD};
+    /// let payments = [Dec!(17.5) * EUR, Dec!(4.25) * USD];
+    /// let total: Result<Money, MoneyError> = payments.iter().sum();
+    /// assert_eq!(
+    ///     total,
+    ///     Err(MoneyError::CurrencyMismatch {
+    ///         expected: EUR,
+    ///         found: USD
+    ///     })
+    /// );
+    /// ```
+    #[inline]
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Self {
+        Money::try_sum(iter)
+    }
+}
@@ -0,0 +1,259 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::vec::Vec;
+
+use fpdec::{Decimal, DivRounded};
+
+use crate::{math::powi, Money, Percent, Quantity};
+
+/// Method of repaying a loan.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Amortization {
+    /// All installments have the same amount (apart from the last one,
+    /// which absorbs rounding differences); the share of principal grows
+    /// from period to period.
+    Annuity,
+    /// The principal is repaid in equal parts (apart from the last one,
+    /// which absorbs rounding differences); the interest is paid in addition.
+    Linear,
+}
+
+/// Single row of an amortization schedule.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Installment {
+    number: u32,
+    payment: Money,
+    interest: Money,
+    principal: Money,
+    balance: Money,
+}
+
+impl Installment {
+    /// The number of the installment, starting with 1
+    #[inline(always)]
+    #[must_use]
+    pub const fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// The total amount paid, i.e. interest plus principal
+    #[inline(always)]
+    #[must_use]
+    pub const fn payment(&self) -> Money {
+        self.payment
+    }
+
+    /// The interest paid
+    #[inline(always)]
+    #[must_use]
+    pub const fn interest(&self) -> Money {
+        self.interest
+    }
+
+    /// The principal repaid
+    #[inline(always)]
+    #[must_use]
+    pub const fn principal(&self) -> Money {
+        self.principal
+    }
+
+    /// The remaining balance after the installment
+    #[inline(always)]
+    #[must_use]
+    pub const fn balance(&self) -> Money {
+        self.balance
+    }
+}
+
+/// Schedule of the installments repaying a loan.
+///
+/// The interest of each period is calculated on the balance outstanding at
+/// the beginning of the period, at the annual rate divided by the number of
+/// periods per year, and rounded to the minor unit of the currency. The
+/// final installment repays the remaining balance, so that the principal
+/// components always sum up exactly to the loan amount. An installment never
+/// repays more than the outstanding balance, so that in case of a small
+/// principal the balance may reach zero before the final installment.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{AmortizationSchedule, Dec, Decimal, Percent, EUR};
+/// let schedule = AmortizationSchedule::annuity(
+///     Dec!(10000) * EUR,
+///     Percent::new(Dec!(6)),
+///     12,
+///     12,
+/// );
+/// let first = schedule.installments()[0];
+/// assert_eq!(first.payment(), Dec!(860.66) * EUR);
+/// assert_eq!(first.interest(), Dec!(50) * EUR);
+/// assert_eq!(first.principal(), Dec!(810.66) * EUR);
+/// assert_eq!(first.balance(), Dec!(9189.34) * EUR);
+/// assert!(schedule.installments()[11].balance().is_zero());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmortizationSchedule {
+    amortization: Amortization,
+    installments: Vec<Installment>,
+}
+
+impl AmortizationSchedule {
+    /// Returns the schedule repaying `principal` in `n_periods`
+    /// installments, according to the given method of amortization.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `principal` <= 0
+    /// * `annual_rate` < 0
+    /// * `n_periods` == 0
+    /// * `periods_per_year` == 0
+    #[must_use]
+    pub fn new(
+        amortization: Amortization,
+        principal: Money,
+        annual_rate: Percent,
+        n_periods: u32,
+        periods_per_year: u16,
+    ) -> Self {
+        assert!(principal.is_positive(), "Principal must be > 0.");
+        assert!(
+            !annual_rate.value().is_negative(),
+            "Interest rate must not be negative."
+        );
+        assert!(n_periods > 0, "Number of periods must be >= 1.");
+        assert!(periods_per_year > 0, "Periods per year must be >= 1.");
+        let period_rate =
+            annual_rate.as_fraction() / Decimal::from(periods_per_year);
+        let currency = principal.unit();
+        let minor_unit = currency.minor_unit();
+        // amount paid per period: total payment in case of an annuity,
+        // principal in case of linear amortization
+        let amount = match amortization {
+            Amortization::Annuity if !period_rate.eq_zero() => {
+                let factor =
+                    powi(Decimal::ONE + period_rate, n_periods.into());
+                (principal.amount() * period_rate * factor)
+                    .div_rounded(factor - Decimal::ONE, minor_unit)
+            }
+            _ => principal.amount().div_rounded(n_periods, minor_unit),
+        };
+        let amount = Money::new(amount, currency);
+        let mut balance = principal;
+        let installments = (1..=n_periods)
+            .map(|number| {
+                let interest = balance * period_rate;
+                // the amount per period is rounded, so it may exceed the
+                // remaining balance before the last period
+                let repaid = if number == n_periods {
+                    balance
+                } else {
+                    let repaid = match amortization {
+                        Amortization::Annuity => amount - interest,
+                        Amortization::Linear => amount,
+                    };
+                    if repaid > balance {
+                        balance
+                    } else {
+                        repaid
+                    }
+                };
+                balance -= repaid;
+                Installment {
+                    number,
+                    payment: interest + repaid,
+                    interest,
+                    principal: repaid,
+                    balance,
+                }
+            })
+            .collect();
+        Self {
+            amortization,
+            installments,
+        }
+    }
+
+    /// Returns the annuity schedule repaying `principal` in `n_periods`
+    /// installments.
+    ///
+    /// See `AmortizationSchedule::new` for details.
+    #[inline]
+    #[must_use]
+    pub fn annuity(
+        principal: Money,
+        annual_rate: Percent,
+        n_periods: u32,
+        periods_per_year: u16,
+    ) -> Self {
+        Self::new(
+            Amortization::Annuity,
+            principal,
+            annual_rate,
+            n_periods,
+            periods_per_year,
+        )
+    }
+
+    /// Returns the schedule repaying `principal` in `n_periods` equal parts.
+    ///
+    /// See `AmortizationSchedule::new` for details.
+    #[inline]
+    #[must_use]
+    pub fn linear(
+        principal: Money,
+        annual_rate: Percent,
+        n_periods: u32,
+        periods_per_year: u16,
+    ) -> Self {
+        Self::new(
+            Amortization::Linear,
+            principal,
+            annual_rate,
+            n_periods,
+            periods_per_year,
+        )
+    }
+
+    /// The method of amortization
+    #[inline(always)]
+    #[must_use]
+    pub const fn amortization(&self) -> Amortization {
+        self.amortization
+    }
+
+    /// The installments, in order of payment
+    #[inline(always)]
+    #[must_use]
+    pub fn installments(&self) -> &[Installment] {
+        &self.installments
+    }
+
+    /// The sum of all interest payments
+    #[must_use]
+    pub fn total_interest(&self) -> Money {
+        self.installments
+            .iter()
+            .fold(self.zero(), |acc, inst| acc + inst.interest)
+    }
+
+    /// The sum of all payments
+    #[must_use]
+    pub fn total_payments(&self) -> Money {
+        self.installments
+            .iter()
+            .fold(self.zero(), |acc, inst| acc + inst.payment)
+    }
+
+    #[inline]
+    fn zero(&self) -> Money {
+        Money::zero(self.installments[0].payment.unit())
+    }
+}
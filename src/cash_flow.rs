@@ -0,0 +1,314 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use fpdec::{CheckedAdd, Decimal, Round};
+
+use crate::{
+    aggregate::check_currency,
+    math::{checked_mul, pow},
+    AmountT, Currency, Date, Money, MoneyError, Percent, Quantity,
+};
+
+// Rates used to search for a sign change of the net present value
+const RATE_GRID: [Decimal; 15] = [
+    Decimal::new_raw(-999_999, 6),
+    Decimal::new_raw(-9, 1),
+    Decimal::new_raw(-5, 1),
+    Decimal::new_raw(-2, 1),
+    Decimal::ZERO,
+    Decimal::new_raw(1, 1),
+    Decimal::new_raw(2, 1),
+    Decimal::new_raw(5, 1),
+    Decimal::ONE,
+    Decimal::TWO,
+    Decimal::new_raw(5, 0),
+    Decimal::TEN,
+    Decimal::new_raw(100, 0),
+    Decimal::new_raw(10_000, 0),
+    Decimal::new_raw(1_000_000, 0),
+];
+// Precision of the internal rate of return (as fraction)
+const IRR_PREC: i8 = 12;
+const IRR_EPS: Decimal = Decimal::new_raw(1, 14);
+const IRR_MAX_ITER: u32 = 128;
+
+/// Returns the net present value of the given cash flows, discounted at
+/// `rate` per period and rounded to the minor unit of their currency.
+///
+/// The flows are taken to occur at the beginning of equally spaced periods,
+/// in the order of their dates: the flows at the earliest date are not
+/// discounted, the flows at the n-th following date are discounted for n
+/// periods. The actual time between the dates is not taken into account
+/// (see `xnpv` for this).
+///
+/// # Errors
+///
+/// Returns `Err` if `flows` is empty, if the flows are not all in the same
+/// currency or if the result exceeds the range of `Decimal`.
+///
+/// ### Panics
+/// The function panics in the following cases:
+/// * `rate` <= -100 %
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{npv, Date, Dec, Decimal, Percent, EUR};
+/// let flows = [
+///     (Date::new(2023, 1, 1), Dec!(-1000) * EUR),
+///     (Date::new(2024, 1, 1), Dec!(300) * EUR),
+///     (Date::new(2025, 1, 1), Dec!(400) * EUR),
+///     (Date::new(2026, 1, 1), Dec!(500) * EUR),
+/// ];
+/// assert_eq!(npv(Percent::new(Dec!(5)), &flows)?, Dec!(80.44) * EUR);
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+pub fn npv(
+    rate: Percent,
+    flows: &[(Date, Money)],
+) -> Result<Money, MoneyError> {
+    let currency = single_currency(flows)?;
+    let flows = periodic(flows);
+    Ok(Money::new(
+        present_value(discount_rate(rate), &flows)?,
+        currency,
+    ))
+}
+
+/// Returns the net present value of the given dated cash flows, discounted
+/// at the annual `rate` to the earliest date of the flows and rounded to the
+/// minor unit of their currency.
+///
+/// The time between the earliest date and the date of a flow is measured in
+/// years according to ACT/365 Fixed.
+///
+/// # Errors
+///
+/// Returns `Err` if `flows` is empty, if the flows are not all in the same
+/// currency or if the result exceeds the range of `Decimal`.
+///
+/// ### Panics
+/// The function panics in the following cases:
+/// * `rate` <= -100 %
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{xnpv, Date, Dec, Decimal, Percent, EUR};
+/// let flows = [
+///     (Date::new(2023, 1, 1), Dec!(-1000) * EUR),
+///     (Date::new(2024, 1, 1), Dec!(1100) * EUR),
+/// ];
+/// assert!(xnpv(Percent::new(Dec!(10)), &flows)?.is_zero());
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+pub fn xnpv(
+    rate: Percent,
+    flows: &[(Date, Money)],
+) -> Result<Money, MoneyError> {
+    let currency = single_currency(flows)?;
+    let flows = dated(flows);
+    Ok(Money::new(
+        present_value(discount_rate(rate), &flows)?,
+        currency,
+    ))
+}
+
+/// Returns the internal rate of return of the given cash flows, i.e. the
+/// rate per period for which their net present value (see `npv`) is zero.
+///
+/// The rate is determined with a precision of 1e-12 (as fraction). If the
+/// flows change their sign more than once, there may be more than one rate
+/// solving the equation; in this case one of them is returned.
+///
+/// # Errors
+///
+/// Returns `Err` if `flows` is empty, if the flows are not all in the same
+/// currency, if no rate could be found or if an intermediate result exceeds
+/// the range of `Decimal`.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{irr, Date, Dec, Decimal, Percent, EUR};
+/// let flows = [
+///     (Date::new(2023, 1, 1), Dec!(-1000) * EUR),
+///     (Date::new(2025, 1, 1), Dec!(1210) * EUR),
+/// ];
+/// assert_eq!(irr(&flows)?, Percent::new(Dec!(21)));
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+pub fn irr(flows: &[(Date, Money)]) -> Result<Percent, MoneyError> {
+    single_currency(flows)?;
+    find_rate(&periodic(flows))
+}
+
+/// Returns the internal rate of return of the given dated cash flows, i.e.
+/// the annual rate for which their net present value (see `xnpv`) is zero.
+///
+/// The rate is determined with a precision of 1e-12 (as fraction). If the
+/// flows change their sign more than once, there may be more than one rate
+/// solving the equation; in this case one of them is returned.
+///
+/// # Errors
+///
+/// Returns `Err` if `flows` is empty, if the flows are not all in the same
+/// currency, if no rate could be found or if an intermediate result exceeds
+/// the range of `Decimal`.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{xirr, Date, Dec, Decimal, Percent, EUR};
+/// let flows = [
+///     (Date::new(2023, 1, 1), Dec!(-1000) * EUR),
+///     (Date::new(2024, 1, 1), Dec!(1100) * EUR),
+/// ];
+/// assert_eq!(xirr(&flows)?, Percent::new(Dec!(10)));
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+pub fn xirr(flows: &[(Date, Money)]) -> Result<Percent, MoneyError> {
+    single_currency(flows)?;
+    find_rate(&dated(flows))
+}
+
+// Returns the common currency of the given flows.
+fn single_currency(flows: &[(Date, Money)]) -> Result<Currency, MoneyError> {
+    let (_, first) = flows.first().ok_or(MoneyError::Empty)?;
+    for (_, flow) in &flows[1..] {
+        check_currency(first.unit(), flow)?;
+    }
+    Ok(first.unit())
+}
+
+// Converts the given flows into pairs (time, amount), with time given as
+// the number of distinct dates preceding the date of the flow.
+fn periodic(flows: &[(Date, Money)]) -> Vec<(AmountT, AmountT)> {
+    let dates: BTreeSet<Date> = flows.iter().map(|(date, _)| *date).collect();
+    let periods: BTreeMap<Date, u32> =
+        dates.into_iter().zip(0_u32..).collect();
+    flows
+        .iter()
+        .map(|(date, flow)| (Decimal::from(periods[date]), flow.amount()))
+        .collect()
+}
+
+// Converts the given flows into pairs (time, amount), with time given as
+// years since the earliest date of the flows according to ACT/365 Fixed.
+// Pre-condition: `flows` is not empty.
+fn dated(flows: &[(Date, Money)]) -> Vec<(AmountT, AmountT)> {
+    let ref_date = flows
+        .iter()
+        .fold(flows[0].0, |first, (date, _)| first.min(*date));
+    flows
+        .iter()
+        .map(|(date, flow)| {
+            (
+                Decimal::from(ref_date.days_until(date)) / 365,
+                flow.amount(),
+            )
+        })
+        .collect()
+}
+
+fn discount_rate(rate: Percent) -> AmountT {
+    let rate = rate.as_fraction();
+    assert!(rate > Decimal::NEG_ONE, "Rate must be > -100 %.");
+    rate
+}
+
+// Returns the unrounded net present value of the given flows.
+fn present_value(
+    rate: AmountT,
+    flows: &[(AmountT, AmountT)],
+) -> Result<AmountT, MoneyError> {
+    let discount_factor = Decimal::ONE / (Decimal::ONE + rate);
+    sum_of_products(discount_factor, flows, |time| time)
+}
+
+// Returns a value having the same sign as the net present value of the
+// given flows at `rate`.
+// For rates < 0 the present value is compounded to the time of the last
+// flow, so that the powers to be calculated stay <= 1.
+fn scaled_present_value(
+    rate: AmountT,
+    flows: &[(AmountT, AmountT)],
+) -> Result<AmountT, MoneyError> {
+    if rate.is_negative() {
+        let max_time = flows
+            .iter()
+            .map(|(time, _)| *time)
+            .max()
+            .unwrap_or(Decimal::ZERO);
+        let growth_factor = Decimal::ONE + rate;
+        sum_of_products(growth_factor, flows, |time| max_time - time)
+    } else {
+        present_value(rate, flows)
+    }
+}
+
+// Returns the sum of the amounts of the given flows, each multiplied by
+// `factor` raised to the power of `exp(time)`.
+fn sum_of_products(
+    factor: AmountT,
+    flows: &[(AmountT, AmountT)],
+    exp: impl Fn(AmountT) -> AmountT,
+) -> Result<AmountT, MoneyError> {
+    flows.iter().try_fold(Decimal::ZERO, |acc, (time, amount)| {
+        pow(factor, exp(*time))
+            .and_then(|power| checked_mul(*amount, power))
+            .and_then(|value| acc.checked_add(value))
+            .ok_or(MoneyError::Overflow)
+    })
+}
+
+// Returns the rate for which the net present value of the given flows is
+// zero.
+fn find_rate(flows: &[(AmountT, AmountT)]) -> Result<Percent, MoneyError> {
+    // search for an interval of rates giving a sign change
+    let mut lo = RATE_GRID[0];
+    let mut lo_value = scaled_present_value(lo, flows)?;
+    if lo_value.eq_zero() {
+        return Ok(Percent::from_fraction(lo));
+    }
+    let mut hi = None;
+    for rate in &RATE_GRID[1..] {
+        let value = scaled_present_value(*rate, flows)?;
+        if value.signum() != lo_value.signum() {
+            hi = Some(*rate);
+            break;
+        }
+        lo = *rate;
+        lo_value = value;
+    }
+    let mut hi = hi.ok_or(MoneyError::NoConvergence)?;
+    // bisect the interval
+    for _ in 0..IRR_MAX_ITER {
+        let mid: AmountT = (lo + hi) / 2;
+        if hi - lo <= IRR_EPS {
+            return Ok(Percent::from_fraction(mid.round(IRR_PREC)));
+        }
+        let value = scaled_present_value(mid, flows)?;
+        if value.eq_zero() {
+            return Ok(Percent::from_fraction(mid.round(IRR_PREC)));
+        }
+        if value.signum() == lo_value.signum() {
+            lo = mid;
+            lo_value = value;
+        } else {
+            hi = mid;
+        }
+    }
+    Err(MoneyError::NoConvergence)
+}
@@ -0,0 +1,472 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::vec::Vec;
+
+use fpdec::Decimal;
+
+use crate::{
+    exchange::correct_by_steps, AmountT, Currency, ExchangeRate, Money,
+    MoneyError, Percent, Quantity, TargetRounding,
+};
+
+// Max number of iterations used to estimate the amounts needed for a target
+const MAX_ITER: u32 = 64;
+
+/// Fee charged for a currency conversion.
+///
+/// The amount of the fee is the fixed part plus the percentage of the amount
+/// it is applied to, but not less than the minimum. All parts are given in
+/// the same currency, which determines whether the fee is charged on the
+/// source side (deducted from the amount to be converted) or on the target
+/// side (deducted from the converted amount) of a conversion.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Dec, Decimal, Fee, Percent, EUR};
+/// let fee = Fee::percent(Percent::new(Dec!(1.5)), EUR)
+///     .with_minimum(Dec!(2.50) * EUR);
+/// assert_eq!(fee.amount_for(Dec!(1000) * EUR), Dec!(15) * EUR);
+/// assert_eq!(fee.amount_for(Dec!(100) * EUR), Dec!(2.50) * EUR);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Fee {
+    fixed: Money,
+    percent: Percent,
+    minimum: Money,
+}
+
+impl Fee {
+    /// Returns a fee of the fixed `amount`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `amount` < 0
+    #[must_use]
+    pub fn fixed(amount: Money) -> Self {
+        assert!(!amount.is_negative(), "Fee must not be negative.");
+        Self {
+            fixed: amount,
+            percent: Percent::new(Decimal::ZERO),
+            minimum: Money::zero(amount.unit),
+        }
+    }
+
+    /// Returns a fee of `percent` of the amount it is applied to, charged in
+    /// `currency`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `percent` < 0 or `percent` >= 100
+    #[must_use]
+    pub fn percent(percent: Percent, currency: Currency) -> Self {
+        assert!(
+            !percent.value().is_negative() && percent < Percent::HUNDRED,
+            "Percentage must be >= 0 and < 100."
+        );
+        Self {
+            fixed: Money::zero(currency),
+            percent,
+            minimum: Money::zero(currency),
+        }
+    }
+
+    /// Returns `self` with `percent` of the amount it is applied to added.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `percent` < 0 or `percent` >= 100
+    #[must_use]
+    pub fn with_percent(self, percent: Percent) -> Self {
+        Self {
+            percent: Self::percent(percent, self.currency()).percent,
+            ..self
+        }
+    }
+
+    /// Returns `self` with the given minimum amount.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * the currency of `minimum` differs from the currency of `self`
+    /// * `minimum` < 0
+    #[must_use]
+    pub fn with_minimum(self, minimum: Money) -> Self {
+        assert_eq!(
+            minimum.unit,
+            self.currency(),
+            "Currency of minimum must match currency of fee."
+        );
+        assert!(!minimum.is_negative(), "Minimum must not be negative.");
+        Self { minimum, ..self }
+    }
+
+    /// The currency the fee is charged in
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.fixed.unit
+    }
+
+    /// The fixed part of the fee
+    #[inline(always)]
+    #[must_use]
+    pub const fn fixed_amount(&self) -> Money {
+        self.fixed
+    }
+
+    /// The variable part of the fee
+    #[inline(always)]
+    #[must_use]
+    pub const fn percentage(&self) -> Percent {
+        self.percent
+    }
+
+    /// The minimum amount of the fee
+    #[inline(always)]
+    #[must_use]
+    pub const fn minimum(&self) -> Money {
+        self.minimum
+    }
+
+    /// Returns the amount of the fee applied to `base`, rounded to the minor
+    /// unit of its currency.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * the currency of `base` differs from the currency of `self`
+    #[must_use]
+    pub fn amount_for(&self, base: Money) -> Money {
+        let amount = self.fixed + base * self.percent;
+        if amount < self.minimum {
+            self.minimum
+        } else {
+            amount
+        }
+    }
+}
+
+/// Terms applied when converting `Money` from one currency into another:
+/// an `ExchangeRate` (the mid rate), a markup on this rate and fees.
+///
+/// The conversion of a source amount is done in the following steps:
+/// * The fees in source currency are deducted from the source amount.
+/// * The remaining amount is converted at the applied rate, i.e. the mid
+///   rate reduced by the markup.
+/// * The fees in target currency are deducted from the converted amount.
+///
+/// Both currencies of the exchange rate can be used as source currency.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{
+/// #     ConversionTerms, Dec, Decimal, ExchangeRate, Fee, Percent, EUR, USD,
+/// # };
+/// let terms = ConversionTerms::new(ExchangeRate::new(EUR, 1, USD, Dec!(1.08)))
+///     .with_markup(Percent::new(Dec!(2)))
+///     .with_fee(Fee::fixed(Dec!(1.50) * EUR));
+/// let quote = terms.quote(Dec!(500) * EUR)?;
+/// assert_eq!(quote.source_fee(), Dec!(1.50) * EUR);
+/// assert_eq!(quote.applied_rate(), Dec!(1.0584));
+/// assert_eq!(quote.markup(), Dec!(10.77) * USD);
+/// assert_eq!(quote.target(), Dec!(527.61) * USD);
+/// let quote = terms.quote_for_target(Dec!(100) * USD)?;
+/// assert_eq!(quote.source(), Dec!(95.98) * EUR);
+/// assert_eq!(quote.target(), Dec!(100) * USD);
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConversionTerms {
+    rate: ExchangeRate,
+    markup: Percent,
+    fees: Vec<Fee>,
+}
+
+impl ConversionTerms {
+    /// Returns new conversion terms using `rate` without markup and fees.
+    #[must_use]
+    pub const fn new(rate: ExchangeRate) -> Self {
+        Self {
+            rate,
+            markup: Percent::new(Decimal::ZERO),
+            fees: Vec::new(),
+        }
+    }
+
+    /// Returns `self` with the given markup on the exchange rate.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `markup` < 0 or `markup` >= 100
+    #[must_use]
+    pub fn with_markup(self, markup: Percent) -> Self {
+        assert!(
+            !markup.value().is_negative() && markup < Percent::HUNDRED,
+            "Markup must be >= 0 and < 100."
+        );
+        Self { markup, ..self }
+    }
+
+    /// Returns `self` with `fee` added.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * the currency of `fee` is neither the unit currency nor the term
+    ///   currency of the exchange rate
+    #[must_use]
+    pub fn with_fee(mut self, fee: Fee) -> Self {
+        assert!(
+            fee.currency() == self.rate.unit_currency()
+                || fee.currency() == self.rate.term_currency(),
+            "Currency of fee must be one of the currencies of the rate."
+        );
+        self.fees.push(fee);
+        self
+    }
+
+    /// The exchange rate (mid rate)
+    #[inline(always)]
+    #[must_use]
+    pub const fn rate(&self) -> ExchangeRate {
+        self.rate
+    }
+
+    /// The markup on the exchange rate
+    #[inline(always)]
+    #[must_use]
+    pub const fn markup(&self) -> Percent {
+        self.markup
+    }
+
+    /// The fees
+    #[inline(always)]
+    #[must_use]
+    pub fn fees(&self) -> &[Fee] {
+        &self.fees
+    }
+
+    /// Returns the quote for converting `source` into the other currency of
+    /// the exchange rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * the currency of `source` is neither the unit currency nor the term
+    ///   currency of the exchange rate
+    /// * the fees exceed the amount they are applied to
+    pub fn quote(
+        &self,
+        source: Money,
+    ) -> Result<ConversionQuote, MoneyError> {
+        let target_currency = self.other_currency(source.unit)?;
+        let mid_rate = self.mid_rate(source.unit);
+        let factor = Decimal::ONE - self.markup.as_fraction();
+        let source_fee = self.fee_for(source);
+        let net = source - source_fee;
+        if net.is_negative() {
+            return Err(MoneyError::FeeExceedsAmount {
+                amount: source,
+                fee: source_fee,
+            });
+        }
+        let at_mid_rate = self.convert(net.amount, source.unit);
+        let converted = Money::new(at_mid_rate * factor, target_currency);
+        let markup = Money::new(at_mid_rate, target_currency) - converted;
+        let target_fee = self.fee_for(converted);
+        let target = converted - target_fee;
+        if target.is_negative() {
+            return Err(MoneyError::FeeExceedsAmount {
+                amount: converted,
+                fee: target_fee,
+            });
+        }
+        Ok(ConversionQuote {
+            source,
+            source_fee,
+            mid_rate,
+            applied_rate: mid_rate * factor,
+            markup,
+            target_fee,
+            target,
+        })
+    }
+
+    /// Returns the quote for the smallest source amount resulting in at least
+    /// `target` after conversion.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * `target` <= 0
+    /// * the currency of `target` is neither the unit currency nor the term
+    ///   currency of the exchange rate
+    /// * no source amount resulting in `target` could be determined
+    pub fn quote_for_target(
+        &self,
+        target: Money,
+    ) -> Result<ConversionQuote, MoneyError> {
+        if !target.is_positive() {
+            return Err(MoneyError::NotPositive(target));
+        }
+        let source_currency = self.other_currency(target.unit)?;
+        let factor = Decimal::ONE - self.markup.as_fraction();
+        // calculate the smallest net amount which, converted at the applied
+        // rate, gives the amount needed to cover the target fees ...
+        let applied_rate = if source_currency == self.rate.unit_currency() {
+            self.rate.scaled(factor)
+        } else {
+            self.rate.scaled(Decimal::ONE / factor)
+        };
+        let net = applied_rate.source_for_target(
+            self.gross_for_net(target),
+            TargetRounding::AtLeast,
+        )?;
+        // ... add the source fees and correct the result for the rounding
+        // of the fees
+        let step = Money::new(
+            Decimal::new_raw(1, source_currency.minor_unit()),
+            source_currency,
+        );
+        let source =
+            correct_by_steps(self.gross_for_net(net), step, |source| {
+                self.quote(source).is_ok_and(|quote| quote.target >= target)
+            })?;
+        self.quote(source)
+    }
+
+    // Returns the other currency of the exchange rate.
+    fn other_currency(
+        &self,
+        currency: Currency,
+    ) -> Result<Currency, MoneyError> {
+        if currency == self.rate.unit_currency() {
+            Ok(self.rate.term_currency())
+        } else if currency == self.rate.term_currency() {
+            Ok(self.rate.unit_currency())
+        } else {
+            Err(MoneyError::CurrencyMismatch {
+                expected: self.rate.unit_currency(),
+                found: currency,
+            })
+        }
+    }
+
+    // Returns the amount of the other currency equivalent to one unit of
+    // `currency`, according to the exchange rate.
+    fn mid_rate(&self, currency: Currency) -> AmountT {
+        if currency == self.rate.unit_currency() {
+            self.rate.rate()
+        } else {
+            self.rate.inverse_rate()
+        }
+    }
+
+    // Returns the equivalent of `amount` in the currency other than
+    // `currency`, converted at the mid rate (like `Money * ExchangeRate` and
+    // `Money / ExchangeRate`).
+    fn convert(&self, amount: AmountT, currency: Currency) -> AmountT {
+        if currency == self.rate.unit_currency() {
+            amount * self.rate.rate()
+        } else {
+            amount / self.rate.rate()
+        }
+    }
+
+    // Returns the sum of the fees in the currency of `base` applied to
+    // `base`.
+    fn fee_for(&self, base: Money) -> Money {
+        self.fees
+            .iter()
+            .filter(|fee| fee.currency() == base.unit)
+            .fold(Money::zero(base.unit), |acc, fee| {
+                acc + fee.amount_for(base)
+            })
+    }
+
+    // Returns (an estimate of) the amount which equals `net` after deduction
+    // of the fees in the currency of `net`.
+    fn gross_for_net(&self, net: Money) -> Money {
+        let mut gross = net;
+        for _ in 0..MAX_ITER {
+            let next = net + self.fee_for(gross);
+            if next == gross {
+                break;
+            }
+            gross = next;
+        }
+        gross
+    }
+}
+
+/// Breakdown of a currency conversion according to `ConversionTerms`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ConversionQuote {
+    source: Money,
+    source_fee: Money,
+    mid_rate: AmountT,
+    applied_rate: AmountT,
+    markup: Money,
+    target_fee: Money,
+    target: Money,
+}
+
+impl ConversionQuote {
+    /// The amount to be converted, including the fees in source currency
+    #[inline(always)]
+    #[must_use]
+    pub const fn source(&self) -> Money {
+        self.source
+    }
+
+    /// The fees charged in source currency
+    #[inline(always)]
+    #[must_use]
+    pub const fn source_fee(&self) -> Money {
+        self.source_fee
+    }
+
+    /// The amount of target currency equivalent to one unit of source
+    /// currency, according to the exchange rate
+    #[inline(always)]
+    #[must_use]
+    pub const fn mid_rate(&self) -> AmountT {
+        self.mid_rate
+    }
+
+    /// The mid rate reduced by the markup
+    #[inline(always)]
+    #[must_use]
+    pub const fn applied_rate(&self) -> AmountT {
+        self.applied_rate
+    }
+
+    /// The difference between the amounts converted at the mid rate and at
+    /// the applied rate, in target currency
+    #[inline(always)]
+    #[must_use]
+    pub const fn markup(&self) -> Money {
+        self.markup
+    }
+
+    /// The fees charged in target currency
+    #[inline(always)]
+    #[must_use]
+    pub const fn target_fee(&self) -> Money {
+        self.target_fee
+    }
+
+    /// The amount received, after deduction of all fees
+    #[inline(always)]
+    #[must_use]
+    pub const fn target(&self) -> Money {
+        self.target
+    }
+}
@@ -8,124 +8,590 @@
 // $Revision$
 
 use alloc::{
-    borrow::ToOwned, collections::BTreeMap, format, string::String, vec::Vec,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{
+    fmt,
+    ops::Deref,
+    str::FromStr,
+    sync::atomic::{AtomicU32, Ordering as AtomicOrdering},
 };
-use core::str::FromStr;
 #[cfg(feature = "std")]
-use std::sync::RwLock;
+use std::sync::{PoisonError, RwLock};
 
+use fpdec::MAX_N_FRAC_DIGITS;
 #[cfg(not(feature = "std"))]
 use synctools::rwlock::RwLock;
 
 use crate::{
-    currency_info::{CurrencyInfo, CurrencyKey, CurrencyKeyError},
-    ISOCurrency,
+    currency_info::{
+        CurrencyInfo, CurrencyKey, CurrencyKeyError, SymbolRules,
+    },
+    AmountT, ISOCurrency, Money, MoneyError, Quantity,
 };
 
+/// Identifies the registry a custom currency has been registered in.
+///
+/// ISO 4217 currencies are the same in all registries; they are tagged like
+/// the currencies registered in the global registry.
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash,
+)]
+pub(crate) struct RegistryId(u32);
+
+impl RegistryId {
+    const GLOBAL: Self = Self(0);
+}
+
+static NEXT_REGISTRY_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Index of the databases of all registries other than the global one, so
+/// that the attributes of a currency can be looked up from the currency.
+///
+/// The index holds weak references only, so that the database of a registry
+/// gets freed when the registry is dropped.
+static REGISTRY_INDEX: RwLock<
+    BTreeMap<RegistryId, Weak<RwLock<CurrencyDB>>>,
+> = RwLock::new(BTreeMap::new());
+
+/// Attributes reported for a custom currency whose registry has been
+/// dropped.
+const UNKNOWN_CURRENCY_INFO: CurrencyInfo = CurrencyInfo {
+    key: CurrencyKey::UNKNOWN,
+    symbol: "???",
+    name: "Unknown Currency",
+    minor_unit: MAX_N_FRAC_DIGITS,
+};
+
+fn iso_curr_info(key: CurrencyKey) -> Option<CurrencyInfo> {
+    let props = ISOCurrency::from_key(key)?.info();
+    Some(CurrencyInfo::new(props.0, props.1, props.2))
+}
+
+/// Returns the attributes of the given currency.
+pub(crate) fn currency_info(curr: &Currency) -> CurrencyInfo {
+    if curr.registry == RegistryId::GLOBAL {
+        return iso_curr_info(curr.key)
+            .or_else(|| crate::CURRENCY_REGISTRY.custom_currency_info(curr))
+            .unwrap_or(UNKNOWN_CURRENCY_INFO);
+    }
+    #[cfg(feature = "std")]
+    let index = REGISTRY_INDEX
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    #[cfg(not(feature = "std"))]
+    let index = REGISTRY_INDEX.read();
+    let db = index.get(&curr.registry).and_then(Weak::upgrade);
+    drop(index);
+    db.and_then(|db| {
+        #[cfg(feature = "std")]
+        let db = db.read().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let db = db.read();
+        db.currency_infos.get(&curr.key).copied()
+    })
+    .unwrap_or(UNKNOWN_CURRENCY_INFO)
+}
+
+/// Change of a `CurrencyRegistry`, reported to the listeners subscribed to
+/// the registry.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CurrencyEvent {
+    /// The currency has been registered.
+    Registered(Currency),
+    /// Name and / or minor unit of the currency have been changed.
+    Updated(Currency),
+    /// The currency has been deregistered.
+    Deregistered(Currency),
+}
+
+type Listener = Arc<dyn Fn(&CurrencyEvent) + Send + Sync>;
+
+/// Strings used as symbols or names of custom currencies.
+///
+/// The strings are leaked, so that they can be handed out as `&'static str`
+/// without further allocations. Each distinct string is leaked only once, so
+/// the memory used is bounded by the number of different symbols and names,
+/// regardless of how often currencies are registered or updated.
+static INTERNED_STRINGS: RwLock<BTreeSet<&'static str>> =
+    RwLock::new(BTreeSet::new());
+
+fn intern(s: &str) -> &'static str {
+    #[cfg(feature = "std")]
+    let strings = INTERNED_STRINGS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    #[cfg(not(feature = "std"))]
+    let strings = INTERNED_STRINGS.read();
+    if let Some(interned) = strings.get(s) {
+        return interned;
+    }
+    // Release the read lock and get a write lock.
+    drop(strings);
+    #[cfg(feature = "std")]
+    let mut strings = INTERNED_STRINGS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    #[cfg(not(feature = "std"))]
+    let mut strings = INTERNED_STRINGS.write();
+    // Maybe another thread interned it in the meantime, so check again!
+    if let Some(interned) = strings.get(s) {
+        return interned;
+    }
+    let interned: &'static str = String::from(s).leak();
+    strings.insert(interned);
+    interned
+}
+
 struct CurrencyDB {
     registered_currencies: Vec<Currency>,
-    currency_info_map: BTreeMap<Currency, CurrencyInfo>,
-    symbol_currency_map: BTreeMap<String, Currency>,
+    symbol_currency_map: BTreeMap<&'static str, Currency>,
+    // Attributes of all custom currencies registered so far, including
+    // those deregistered, so that existing `Money` values remain usable.
+    currency_infos: BTreeMap<CurrencyKey, CurrencyInfo>,
 }
 
 impl CurrencyDB {
-    fn insert(&mut self, curr: Currency, info: &CurrencyInfo) {
-        let symbol = info.symbol.clone();
+    const fn new() -> Self {
+        Self {
+            registered_currencies: Vec::new(),
+            symbol_currency_map: BTreeMap::new(),
+            currency_infos: BTreeMap::new(),
+        }
+    }
+
+    fn insert(&mut self, curr: Currency, symbol: &'static str) {
         self.registered_currencies.push(curr);
-        self.currency_info_map.insert(curr, info.to_owned());
         self.symbol_currency_map.insert(symbol, curr);
     }
+
+    fn remove(&mut self, curr: &Currency) {
+        self.registered_currencies.retain(|c| c != curr);
+        self.symbol_currency_map.retain(|_, c| c != curr);
+    }
+
+    // Checks that the custom currency `curr` is registered. Its symbol is
+    // taken from `self`, because the registry is locked.
+    fn check_registered(
+        &self,
+        curr: &Currency,
+    ) -> Result<(), CurrencyKeyError> {
+        if !self.registered_currencies.contains(curr) {
+            let symbol = self
+                .currency_infos
+                .get(&curr.key)
+                .map_or(UNKNOWN_CURRENCY_INFO.symbol, |info| info.symbol);
+            return Err(CurrencyKeyError::NotRegistered { symbol });
+        }
+        Ok(())
+    }
 }
 
-pub(crate) struct CurrencyRegistry {
-    inner: RwLock<CurrencyDB>,
+// The database of the global registry is a static, those of other
+// registries are shared with the `REGISTRY_INDEX`.
+enum RegistryDB {
+    Static(RwLock<CurrencyDB>),
+    Shared(Arc<RwLock<CurrencyDB>>),
+}
+
+impl Deref for RegistryDB {
+    type Target = RwLock<CurrencyDB>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Static(db) => db,
+            Self::Shared(db) => db,
+        }
+    }
+}
+
+/// Registry of currencies, which can be looked up by their symbol.
+///
+/// There is a global registry, which is used by `Currency::new`,
+/// `Currency::from_symbol` and `Currency::iter`. It can be accessed by
+/// calling `CurrencyRegistry::global`.
+///
+/// Additional registries can be created by calling `CurrencyRegistry::new`.
+/// This allows to use different sets of currencies in different contexts,
+/// for example for different tenants of an application or in tests which
+/// must not interfere with each other.
+///
+/// The currencies defined in ISO 4217 are implicitly known to every
+/// registry. They get registered when first looked up by their symbol.
+///
+/// The attributes (symbol, name and minor unit) of the custom currencies
+/// are held by the registry they are registered in. Custom currencies
+/// registered in different registries are different currencies, even if
+/// they have the same symbol, so different tenants can define currencies
+/// with the same symbol but different names or minor units. When a registry
+/// is dropped, the attributes of its custom currencies are dropped as well;
+/// `Money` values in these currencies should not be used anymore, their
+/// currency is reported as "???" and amounts are no longer rounded.
+///
+/// Custom currencies can be changed by `CurrencyRegistry::update_currency`
+/// and removed by `CurrencyRegistry::deregister_currency`. Listeners
+/// subscribed by `CurrencyRegistry::subscribe` get notified about these
+/// changes.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Currency, CurrencyRegistry, Dec, Decimal};
+/// let registry = CurrencyRegistry::new();
+/// let pts = registry.register_currency("PTS", "Points", 0).unwrap();
+/// assert_eq!(registry.currency_from_symbol("PTS"), Some(pts));
+/// assert_eq!(CurrencyRegistry::global().currency_from_symbol("PTS"), None);
+/// assert_eq!((Dec!(17.4) * pts).to_string(), "17 PTS");
+/// ```
+pub struct CurrencyRegistry {
+    id: RegistryId,
+    symbol_rules: SymbolRules,
+    inner: RegistryDB,
+    listeners: RwLock<Vec<Listener>>,
 }
 
 impl CurrencyRegistry {
-    pub(crate) const fn new() -> Self {
+    /// Returns the global registry.
+    pub(crate) const fn new_global() -> Self {
         Self {
-            inner: RwLock::new(CurrencyDB {
-                registered_currencies: Vec::new(),
-                currency_info_map: BTreeMap::new(),
-                symbol_currency_map: BTreeMap::new(),
-            }),
+            id: RegistryId::GLOBAL,
+            symbol_rules: SymbolRules::DEFAULT,
+            inner: RegistryDB::Static(RwLock::new(CurrencyDB::new())),
+            listeners: RwLock::new(Vec::new()),
         }
     }
 
-    #[allow(clippy::unwrap_in_result)]
-    pub(crate) fn register_currency(
+    /// Returns a new, empty registry, applying the default `SymbolRules`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_symbol_rules(SymbolRules::DEFAULT)
+    }
+
+    /// Returns a new, empty registry, applying the given `SymbolRules` to
+    /// the symbols of currencies to be registered.
+    #[must_use]
+    pub fn with_symbol_rules(symbol_rules: SymbolRules) -> Self {
+        let id = RegistryId(
+            NEXT_REGISTRY_ID.fetch_add(1, AtomicOrdering::Relaxed),
+        );
+        let db = Arc::new(RwLock::new(CurrencyDB::new()));
+        #[cfg(feature = "std")]
+        let mut index = REGISTRY_INDEX
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let mut index = REGISTRY_INDEX.write();
+        index.insert(id, Arc::downgrade(&db));
+        Self {
+            id,
+            symbol_rules,
+            inner: RegistryDB::Shared(db),
+            listeners: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Returns the `SymbolRules` applied by `self`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn symbol_rules(&self) -> SymbolRules {
+        self.symbol_rules
+    }
+
+    /// Returns a reference to the global registry.
+    #[inline(always)]
+    #[must_use]
+    pub fn global() -> &'static Self {
+        &crate::CURRENCY_REGISTRY
+    }
+
+    /// Registers a `Currency` with the given attributes in `self` and
+    /// returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if
+    /// * the given symbol does not comply with the `SymbolRules` of `self`,
+    /// * a `Currency` with the given symbol is already registered in
+    ///   `self`,
+    /// * the given symbol is the symbol of an ISO 4217 currency with
+    ///   different attributes,
+    /// * the key derived from the given symbol collides with the key of a
+    ///   currency with a different symbol.
+    pub fn register_currency(
         &self,
         symbol: &str,
         name: &str,
         minor_units: u8,
     ) -> Result<Currency, CurrencyKeyError> {
-        let key = CurrencyKey::from_str(symbol)?;
-        let info = CurrencyInfo {
-            key,
-            symbol: symbol.into(),
-            name: name.into(),
-            minor_unit: minor_units,
-        };
-        let curr = Currency { key };
+        self.symbol_rules.validate(symbol)?;
+        let key = CurrencyKey::from_symbol(symbol);
         #[cfg(feature = "std")]
-        let mut db = self.inner.write().unwrap();
+        let mut db =
+            self.inner.write().unwrap_or_else(PoisonError::into_inner);
         #[cfg(not(feature = "std"))]
         let mut db = self.inner.write();
-        if db.currency_info_map.contains_key(&curr) {
-            let msg = format!(
-                "Currency with key derived from '{symbol}' already \
-                 registered."
-            );
-            return Err(CurrencyKeyError(msg));
+        let (curr, info) = if let Some(info) = iso_curr_info(key) {
+            if info.name != name || info.minor_unit != minor_units {
+                return Err(CurrencyKeyError::ConflictingDefinition {
+                    symbol: symbol.into(),
+                });
+            }
+            (
+                Currency {
+                    key,
+                    registry: RegistryId::GLOBAL,
+                },
+                info,
+            )
+        } else {
+            let curr = Currency {
+                key,
+                registry: self.id,
+            };
+            let info = match db.currency_infos.get(&key) {
+                Some(info) if info.symbol != symbol => {
+                    return Err(CurrencyKeyError::Collision {
+                        symbol: symbol.into(),
+                        existing: info.symbol,
+                    });
+                }
+                // Re-use the strings of a former registration.
+                Some(info) => CurrencyInfo {
+                    name: intern(name),
+                    minor_unit: minor_units,
+                    ..*info
+                },
+                None => CurrencyInfo {
+                    key,
+                    symbol: intern(symbol),
+                    name: intern(name),
+                    minor_unit: minor_units,
+                },
+            };
+            (curr, info)
+        };
+        if db.registered_currencies.contains(&curr) {
+            return Err(CurrencyKeyError::AlreadyRegistered {
+                symbol: symbol.into(),
+            });
         }
-        db.insert(curr, &info);
+        if !curr.is_iso() {
+            db.currency_infos.insert(key, info);
+        }
+        db.insert(curr, info.symbol);
+        drop(db);
+        self.notify(&CurrencyEvent::Registered(curr));
         Ok(curr)
     }
 
-    pub(crate) fn get_registered_currencies(&self) -> Vec<Currency> {
+    /// Changes name and minor unit of the custom currency `curr`, which must
+    /// be registered in `self`.
+    ///
+    /// The change applies to `self` only; currencies with the same symbol
+    /// registered in other registries are not affected.
+    ///
+    /// The amounts of existing `Money` values in currency `curr` are not
+    /// changed, but results of subsequent operations on them are rounded
+    /// according to the new minor unit. Especially, if the minor unit is
+    /// reduced, existing values keep amounts with more fractional digits
+    /// than the new minor unit allows, until they are rounded explicitly.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if
+    /// * `curr` is an ISO 4217 currency,
+    /// * `curr` is not registered in `self`.
+    pub fn update_currency(
+        &self,
+        curr: &Currency,
+        name: &str,
+        minor_units: u8,
+    ) -> Result<(), CurrencyKeyError> {
+        self.check_custom_currency(curr)?;
+        // The write lock prevents concurrent deregistration.
+        #[cfg(feature = "std")]
+        let mut db =
+            self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let mut db = self.inner.write();
+        db.check_registered(curr)?;
+        if let Some(info) = db.currency_infos.get_mut(&curr.key) {
+            info.name = intern(name);
+            info.minor_unit = minor_units;
+        }
+        drop(db);
+        self.notify(&CurrencyEvent::Updated(*curr));
+        Ok(())
+    }
+
+    /// Removes the custom currency `curr` from `self`.
+    ///
+    /// Existing `Money` values in currency `curr` remain valid, but `curr`
+    /// can no longer be looked up from `self`. It can be registered in
+    /// `self` again, possibly with different attributes, which then apply to
+    /// the existing `Money` values as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if
+    /// * `curr` is an ISO 4217 currency,
+    /// * `curr` is not registered in `self`.
+    pub fn deregister_currency(
+        &self,
+        curr: &Currency,
+    ) -> Result<(), CurrencyKeyError> {
+        self.check_custom_currency(curr)?;
         #[cfg(feature = "std")]
-        let db = self.inner.read().unwrap();
+        let mut db =
+            self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let mut db = self.inner.write();
+        db.check_registered(curr)?;
+        db.remove(curr);
+        drop(db);
+        self.notify(&CurrencyEvent::Deregistered(*curr));
+        Ok(())
+    }
+
+    /// Returns the attributes of the custom currency `curr`, if it has been
+    /// registered in `self`.
+    fn custom_currency_info(&self, curr: &Currency) -> Option<CurrencyInfo> {
+        #[cfg(feature = "std")]
+        let db = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let db = self.inner.read();
+        db.currency_infos.get(&curr.key).copied()
+    }
+
+    // Checks that `curr` is a custom currency bound to `self`. This must be
+    // done before locking `self`, because the symbol of a currency bound to
+    // another registry is taken from that registry, which gets locked.
+    fn check_custom_currency(
+        &self,
+        curr: &Currency,
+    ) -> Result<(), CurrencyKeyError> {
+        if curr.is_iso() {
+            return Err(CurrencyKeyError::IsoCurrency {
+                symbol: curr.code(),
+            });
+        }
+        if curr.registry != self.id {
+            return Err(CurrencyKeyError::NotRegistered {
+                symbol: curr.code(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Subscribes `listener` to the changes of `self`.
+    ///
+    /// The listener is called after a currency has been registered, updated
+    /// or deregistered by calling the corresponding method of `self`. It is
+    /// not called when an ISO 4217 currency gets registered implicitly by
+    /// looking it up.
+    ///
+    /// The listener is called without holding any lock of `self`, so it may
+    /// call any method of `self`, including `subscribe`. A listener
+    /// subscribed while an event is reported gets notified about subsequent
+    /// events only.
+    pub fn subscribe<F>(&self, listener: F)
+    where
+        F: Fn(&CurrencyEvent) + Send + Sync + 'static,
+    {
+        #[cfg(feature = "std")]
+        let mut listeners = self
+            .listeners
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let mut listeners = self.listeners.write();
+        listeners.push(Arc::new(listener));
+    }
+
+    fn notify(&self, event: &CurrencyEvent) {
+        // The listeners are called without holding the lock, so that they
+        // can call methods of `self`.
+        #[cfg(feature = "std")]
+        let listeners = self
+            .listeners
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        #[cfg(not(feature = "std"))]
+        let listeners = self.listeners.read().clone();
+        for listener in &listeners {
+            listener(event);
+        }
+    }
+
+    /// Returns the currencies registered in `self`, in the order of their
+    /// registration.
+    #[must_use]
+    pub fn currencies(&self) -> Vec<Currency> {
+        #[cfg(feature = "std")]
+        let db = self.inner.read().unwrap_or_else(PoisonError::into_inner);
         #[cfg(not(feature = "std"))]
         let db = self.inner.read();
         db.registered_currencies.clone()
     }
 
-    pub(crate) fn get_currency_info(&self, curr: &Currency) -> CurrencyInfo {
+    /// Returns the `Money` value represented by `s`, looking up the currency
+    /// in `self`.
+    ///
+    /// `s` must be of the form `<amount> <symbol>`, as produced by
+    /// formatting a `Money` value. The amount is rounded to the minor unit of
+    /// the currency.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{CurrencyRegistry, Dec, Decimal, Quantity};
+    /// let registry = CurrencyRegistry::new();
+    /// let pts = registry.register_currency("PTS", "Points", 0).unwrap();
+    /// let m = registry.parse_money("-1205 PTS").unwrap();
+    /// assert_eq!(m, Dec!(-1205) * pts);
+    /// assert!("-1205 PTS".parse::<moneta::Money>().is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if
+    /// * `s` is not of the form `<amount> <symbol>`,
+    /// * the amount is not a valid decimal literal,
+    /// * there is no currency with the given symbol in `self`.
+    pub fn parse_money(&self, s: &str) -> Result<Money, MoneyError> {
+        let (amnt, sym) = s
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| MoneyError::InvalidFormat { input: s.into() })?;
+        let amount = AmountT::from_str(amnt)?;
+        let sym = sym.trim_start();
+        let curr = self.currency_from_symbol(sym).ok_or_else(|| {
+            MoneyError::UnknownCurrency { symbol: sym.into() }
+        })?;
+        Ok(Money::new(amount, curr))
+    }
+
+    /// Returns `true` if `curr` is registered in `self`.
+    #[must_use]
+    pub fn contains(&self, curr: &Currency) -> bool {
         #[cfg(feature = "std")]
-        let db = self.inner.read().unwrap();
+        let db = self.inner.read().unwrap_or_else(PoisonError::into_inner);
         #[cfg(not(feature = "std"))]
         let db = self.inner.read();
-        match db.currency_info_map.get(curr) {
-            Some(info) => info.clone(),
-            None => {
-                // Can happen only if `self` is a not yet registered ISO
-                // currency, so register it!
-                let iso_curr = ISOCurrency::from_key(curr.key).unwrap();
-                let props = iso_curr.info();
-                let info = CurrencyInfo::new(props.0, props.1, props.2);
-                // Release the read lock and get a write lock.
-                drop(db);
-                #[cfg(feature = "std")]
-                let mut db = self.inner.write().unwrap();
-                #[cfg(not(feature = "std"))]
-                let mut db = self.inner.write();
-                // Maybe another thread registered it in the meantime, so
-                // check again!
-                if !db.currency_info_map.contains_key(curr) {
-                    db.insert(*curr, &info);
-                };
-                info
-            }
-        }
+        db.registered_currencies.contains(curr)
     }
 
-    #[allow(clippy::unwrap_in_result)]
-    pub(crate) fn get_currency_from_symbol(
-        &self,
-        sym: &str,
-    ) -> Option<Currency> {
+    /// Returns `Some(curr)` where `curr.code()` == `sym` and `curr` is
+    /// registered in `self` or is an ISO 4217 currency, otherwise `None`.
+    #[must_use]
+    pub fn currency_from_symbol(&self, sym: &str) -> Option<Currency> {
         #[cfg(feature = "std")]
-        let db = self.inner.read().unwrap();
+        let db = self.inner.read().unwrap_or_else(PoisonError::into_inner);
         #[cfg(not(feature = "std"))]
         let db = self.inner.read();
         match db.symbol_currency_map.get(sym) {
@@ -134,19 +600,20 @@ impl CurrencyRegistry {
                 // Check whether `sym` is the symbol of a not yet registered
                 // ISOCurrency; if so, register and return it!
                 let iso_curr = ISOCurrency::from_symbol(sym)?;
-                let props = iso_curr.info();
-                let info = CurrencyInfo::new(props.0, props.1, props.2);
                 let curr = Currency::from_iso_curr(*iso_curr);
                 // Release the read lock and get a write lock.
                 drop(db);
                 #[cfg(feature = "std")]
-                let mut db = self.inner.write().unwrap();
+                let mut db = self
+                    .inner
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner);
                 #[cfg(not(feature = "std"))]
                 let mut db = self.inner.write();
                 // Maybe another thread registered it in the meantime, so
                 // check again!
-                if !db.currency_info_map.contains_key(&curr) {
-                    db.insert(curr, &info);
+                if !db.registered_currencies.contains(&curr) {
+                    db.insert(curr, iso_curr.symbol());
                 };
                 Some(curr)
             }
@@ -154,14 +621,46 @@ impl CurrencyRegistry {
     }
 }
 
+impl Default for CurrencyRegistry {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for CurrencyRegistry {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        let mut index = REGISTRY_INDEX
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let mut index = REGISTRY_INDEX.write();
+        index.remove(&self.id);
+    }
+}
+
+impl fmt::Debug for CurrencyRegistry {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        form.debug_struct("CurrencyRegistry")
+            .field("currencies", &self.currencies())
+            .finish()
+    }
+}
+
 /// Unit of quantity `Money`.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+///
+/// A custom currency is bound to the registry it has been registered in.
+/// When deserialized, a custom currency refers to the global registry.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[cfg_attr(
     feature = "serde",
     derive(::serde::Deserialize, ::serde::Serialize)
 )]
 pub struct Currency {
     key: CurrencyKey,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    registry: RegistryId,
 }
 
 #[allow(clippy::multiple_inherent_impl)]
@@ -171,6 +670,12 @@ impl Currency {
     pub(crate) const fn from_iso_curr(iso_curr: ISOCurrency) -> Self {
         Self {
             key: iso_curr.key(),
+            registry: RegistryId::GLOBAL,
         }
     }
+
+    /// Returns `true` if `self` is a currency defined in ISO 4217.
+    pub(crate) fn is_iso(&self) -> bool {
+        ISOCurrency::from_key(self.key).is_some()
+    }
 }
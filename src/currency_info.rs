@@ -7,69 +7,376 @@
 // $Source$
 // $Revision$
 
-use alloc::{borrow::ToOwned, format, string::String};
-use core::str::FromStr;
+use alloc::string::String;
+use core::{fmt, str::FromStr};
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+/// Key identifying a currency.
+///
+/// Symbols consisting of at most 8 ascii chars are mapped to keys holding
+/// the bytes of the symbol, so that different symbols always result in
+/// different keys. Other symbols are mapped to keys derived from a hash of
+/// the symbol; these keys have their most significant bit set and can
+/// therefore never collide with a key of the first kind.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[cfg_attr(
     feature = "serde",
     derive(::serde::Deserialize, ::serde::Serialize)
 )]
 pub struct CurrencyKey(u64);
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct CurrencyKeyError(pub String);
+/// Error returned when a currency symbol is invalid or the currency can't be
+/// registered, changed or removed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CurrencyKeyError {
+    /// The symbol is empty.
+    Empty,
+    /// The symbol contains a char not allowed by the `SymbolRules` in
+    /// effect.
+    InvalidChar {
+        /// The symbol given.
+        symbol: String,
+        /// The first char not allowed.
+        ch: char,
+    },
+    /// The number of chars of the symbol is not within the limits set by
+    /// the `SymbolRules` in effect. Symbols are never truncated.
+    InvalidLength {
+        /// The symbol given.
+        symbol: String,
+        /// Minimum number of chars allowed.
+        min_len: usize,
+        /// Maximum number of chars allowed.
+        max_len: usize,
+    },
+    /// The key derived from the symbol is already used by a currency with a
+    /// different symbol.
+    Collision {
+        /// The symbol given.
+        symbol: String,
+        /// The symbol of the currency already using the key.
+        existing: &'static str,
+    },
+    /// A currency with the symbol is already registered.
+    AlreadyRegistered {
+        /// The symbol given.
+        symbol: String,
+    },
+    /// A currency with the symbol is already defined with a different name
+    /// or minor unit.
+    ConflictingDefinition {
+        /// The symbol given.
+        symbol: String,
+    },
+    /// The currency is defined in ISO 4217 and can't be changed.
+    IsoCurrency {
+        /// The symbol of the currency.
+        symbol: &'static str,
+    },
+    /// The currency is not registered.
+    NotRegistered {
+        /// The symbol of the currency.
+        symbol: &'static str,
+    },
+}
+
+impl fmt::Display for CurrencyKeyError {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => form.write_str("The symbol given is empty."),
+            Self::InvalidChar { symbol, ch } => write!(
+                form,
+                "The symbol '{symbol}' contains the invalid char {ch:?}."
+            ),
+            Self::InvalidLength {
+                symbol,
+                min_len,
+                max_len,
+            } => write!(
+                form,
+                "The symbol '{symbol}' must have {min_len} to {max_len} \
+                 chars."
+            ),
+            Self::Collision { symbol, existing } => write!(
+                form,
+                "The key derived from '{symbol}' collides with the key of \
+                 '{existing}'."
+            ),
+            Self::AlreadyRegistered { symbol } => write!(
+                form,
+                "A currency with symbol '{symbol}' is already registered."
+            ),
+            Self::ConflictingDefinition { symbol } => write!(
+                form,
+                "A currency with symbol '{symbol}' is already defined with \
+                 different attributes."
+            ),
+            Self::IsoCurrency { symbol } => {
+                write!(form, "ISO currency '{symbol}' can't be changed.")
+            }
+            Self::NotRegistered { symbol } => {
+                write!(form, "Currency '{symbol}' is not registered.")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CurrencyKeyError {}
+
+/// Rules applied to the symbols of custom currencies.
+///
+/// A symbol is valid if the number of its chars is within the bounds
+/// `min_len` and `max_len` and all of its chars are accepted by the
+/// function `is_allowed_char`.
+///
+/// The default rules accept symbols with 1 to 16 chars, which must not be
+/// white-space or control chars.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{CurrencyRegistry, SymbolRules};
+/// let rules = SymbolRules::new(3, 3, |c| c.is_ascii_uppercase());
+/// let registry = CurrencyRegistry::with_symbol_rules(rules);
+/// assert!(registry.register_currency("XPT", "Points", 0).is_ok());
+/// assert!(registry.register_currency("Pts", "Points", 0).is_err());
+/// assert!(registry.register_currency("XPTS", "Points", 0).is_err());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct SymbolRules {
+    min_len: usize,
+    max_len: usize,
+    is_allowed_char: fn(char) -> bool,
+}
+
+impl SymbolRules {
+    /// Default rules: 1 to 16 chars, no white-space or control chars.
+    pub const DEFAULT: Self =
+        Self::new(1, 16, |c| !c.is_whitespace() && !c.is_control());
+
+    /// Returns a new instance of `SymbolRules`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `min_len` == 0
+    /// * `min_len` > `max_len`
+    #[must_use]
+    pub const fn new(
+        min_len: usize,
+        max_len: usize,
+        is_allowed_char: fn(char) -> bool,
+    ) -> Self {
+        assert!(min_len > 0, "Minimum length must be >= 1.");
+        assert!(
+            min_len <= max_len,
+            "Minimum length must be <= maximum length."
+        );
+        Self {
+            min_len,
+            max_len,
+            is_allowed_char,
+        }
+    }
+
+    /// Minimum number of chars of a symbol
+    #[inline(always)]
+    #[must_use]
+    pub const fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    /// Maximum number of chars of a symbol
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Returns `true` if `ch` is allowed in a symbol.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_allowed_char(&self, ch: char) -> bool {
+        (self.is_allowed_char)(ch)
+    }
+
+    /// Checks whether `symbol` complies with `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if
+    /// * `symbol` is empty,
+    /// * `symbol` contains a char not allowed,
+    /// * the number of chars in `symbol` is not within the limits.
+    pub fn validate(&self, symbol: &str) -> Result<(), CurrencyKeyError> {
+        if symbol.is_empty() {
+            return Err(CurrencyKeyError::Empty);
+        }
+        if let Some(ch) = symbol.chars().find(|c| !self.is_allowed_char(*c)) {
+            return Err(CurrencyKeyError::InvalidChar {
+                symbol: symbol.into(),
+                ch,
+            });
+        }
+        let len = symbol.chars().count();
+        if len < self.min_len || len > self.max_len {
+            return Err(CurrencyKeyError::InvalidLength {
+                symbol: symbol.into(),
+                min_len: self.min_len,
+                max_len: self.max_len,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for SymbolRules {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
 
 impl CurrencyKey {
+    const HASHED: u64 = 1_u64 << 63;
+    /// Key not derived from any valid symbol.
+    pub(crate) const UNKNOWN: Self = Self(0);
+
+    /// Returns the key for the given 3-char ascii code of an ISO currency.
     pub(crate) const fn from_ascii(s: &str) -> Self {
-        debug_assert!(s.is_ascii());
-        debug_assert!(s.len() == 3);
+        assert!(s.is_ascii() && s.len() == 3, "Invalid ISO 4217 code.");
         let b = s.as_bytes();
         let k = [b[0], b[1], b[2], 0_u8, 0_u8, 0_u8, 0_u8, 0_u8];
         Self(u64::from_be_bytes(k))
     }
+
+    /// Returns the key derived from the given symbol, which is assumed to
+    /// be valid.
+    pub(crate) fn from_symbol(s: &str) -> Self {
+        if s.is_ascii() && s.len() <= 8 {
+            let mut k = [0_u8; 8];
+            k[..s.len()].copy_from_slice(s.as_bytes());
+            Self(u64::from_be_bytes(k))
+        } else {
+            // 64-bit FNV-1a hash
+            let hash = s.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+                (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+            });
+            Self(hash | Self::HASHED)
+        }
+    }
 }
 
 impl FromStr for CurrencyKey {
     type Err = CurrencyKeyError;
 
+    /// Returns the key derived from `s`, if `s` complies with the default
+    /// `SymbolRules`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut k = [0_u8; 8];
-        let t = s
-            .trim()
-            .chars()
-            .filter(|c| c.is_ascii())
-            .collect::<String>();
-        match t.len() {
-            0 => Err(CurrencyKeyError(format!(
-                "Given code '{s}' is empty or contains only white-space or \
-                 non-ascii chars."
-            ))),
-            mut l => {
-                l = l.min(8_usize);
-                k[..l].copy_from_slice(&AsRef::<[u8]>::as_ref(&t)[..l]);
-                Ok(Self(u64::from_be_bytes(k)))
-            }
-        }
+        SymbolRules::DEFAULT.validate(s)?;
+        Ok(Self::from_symbol(s))
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Copy, Clone, Debug)]
 pub(crate) struct CurrencyInfo {
     pub(crate) key: CurrencyKey,
-    pub(crate) symbol: String,
-    pub(crate) name: String,
+    pub(crate) symbol: &'static str,
+    pub(crate) name: &'static str,
     pub(crate) minor_unit: u8,
 }
 
 impl CurrencyInfo {
     #[inline]
-    pub(crate) fn new(symbol: &str, name: &str, minor_unit: u8) -> Self {
+    pub(crate) const fn new(
+        symbol: &'static str,
+        name: &'static str,
+        minor_unit: u8,
+    ) -> Self {
         Self {
             key: CurrencyKey::from_ascii(symbol),
-            symbol: symbol.to_owned(),
-            name: name.to_owned(),
+            symbol,
+            name,
             minor_unit,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_from_short_ascii_symbol() {
+        assert_eq!(
+            CurrencyKey::from_symbol("EUR"),
+            CurrencyKey::from_ascii("EUR")
+        );
+        assert_eq!(
+            CurrencyKey::from_symbol("Bitcoin"),
+            CurrencyKey(u64::from_be_bytes(*b"Bitcoin\0"))
+        );
+        assert_eq!(
+            CurrencyKey::from_symbol("BitcoinX"),
+            CurrencyKey(u64::from_be_bytes(*b"BitcoinX"))
+        );
+    }
+
+    #[test]
+    fn test_key_from_long_or_non_ascii_symbol() {
+        let k1 = CurrencyKey::from_symbol("Bitcoin Cash");
+        let k2 = CurrencyKey::from_symbol("Bitcoin Gold");
+        let k3 = CurrencyKey::from_symbol("€UR");
+        assert_ne!(k1, k2);
+        assert_ne!(k3, CurrencyKey::from_symbol("UR"));
+        for k in [k1, k2, k3] {
+            assert_ne!(k.0 & CurrencyKey::HASHED, 0);
+        }
+        assert_eq!(k1, CurrencyKey::from_symbol("Bitcoin Cash"));
+    }
+
+    #[test]
+    fn test_key_from_str() {
+        assert_eq!(
+            "€UR".parse::<CurrencyKey>(),
+            Ok(CurrencyKey::from_symbol("€UR"))
+        );
+        assert_eq!("".parse::<CurrencyKey>(), Err(CurrencyKeyError::Empty));
+        assert_eq!(
+            " EUR".parse::<CurrencyKey>(),
+            Err(CurrencyKeyError::InvalidChar {
+                symbol: " EUR".into(),
+                ch: ' '
+            })
+        );
+        assert_eq!(
+            "ABCDEFGHIJKLMNOPQ".parse::<CurrencyKey>(),
+            Err(CurrencyKeyError::InvalidLength {
+                symbol: "ABCDEFGHIJKLMNOPQ".into(),
+                min_len: 1,
+                max_len: 16
+            })
+        );
+    }
+
+    #[test]
+    fn test_custom_symbol_rules() {
+        let rules = SymbolRules::new(2, 4, |c| c.is_ascii_alphabetic());
+        assert!(rules.validate("ab").is_ok());
+        assert!(rules.validate("abcd").is_ok());
+        assert!(rules.validate("a").is_err());
+        assert!(rules.validate("abcde").is_err());
+        assert!(rules.validate("a1").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_symbol_rules_zero_min_len() {
+        let _r = SymbolRules::new(0, 4, |c| c.is_ascii_alphabetic());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_symbol_rules_min_gt_max() {
+        let _r = SymbolRules::new(5, 4, |c| c.is_ascii_alphabetic());
+    }
+}
@@ -0,0 +1,194 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::fmt;
+
+use crate::MoneyError;
+
+/// Calendar date in the proleptic Gregorian calendar, covering the years 1
+/// to 9999.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::Date;
+/// let start = Date::new(2024, 2, 28);
+/// let end = Date::new(2024, 3, 1);
+/// assert_eq!(start.days_until(&end), 2);
+/// assert_eq!(end.to_string(), "2024-03-01");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize, ::serde::Serialize)
+)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Returns a new instance of `Date`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `year` is not within 1 ..= 9999
+    /// * `month` is not within 1 ..= 12
+    /// * `day` is not within 1 ..= number of days of the given month
+    #[must_use]
+    pub const fn new(year: u16, month: u8, day: u8) -> Self {
+        assert!(Self::is_valid(year, month, day), "Invalid date.");
+        Self { year, month, day }
+    }
+
+    /// Returns a new instance of `Date`, or an error if the given arguments
+    /// do not denote a valid date.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::InvalidDate)` in the following cases:
+    /// * `year` is not within 1 ..= 9999
+    /// * `month` is not within 1 ..= 12
+    /// * `day` is not within 1 ..= number of days of the given month
+    pub const fn try_new(
+        year: u16,
+        month: u8,
+        day: u8,
+    ) -> Result<Self, MoneyError> {
+        if Self::is_valid(year, month, day) {
+            Ok(Self { year, month, day })
+        } else {
+            Err(MoneyError::InvalidDate { year, month, day })
+        }
+    }
+
+    const fn is_valid(year: u16, month: u8, day: u8) -> bool {
+        year >= 1
+            && year <= 9999
+            && month >= 1
+            && month <= 12
+            && day >= 1
+            && day <= days_in_month(year, month)
+    }
+
+    /// The year of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// The month of `self` (1 ..= 12)
+    #[inline(always)]
+    #[must_use]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// The day of the month of `self` (1 ..= 31)
+    #[inline(always)]
+    #[must_use]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns `true` if `self` is the last day of its month.
+    #[inline]
+    #[must_use]
+    pub const fn is_last_day_of_month(&self) -> bool {
+        self.day == days_in_month(self.year, self.month)
+    }
+
+    /// Returns the number of days from `self` to `other`, which is negative
+    /// if `other` is before `self`.
+    #[inline]
+    #[must_use]
+    pub const fn days_until(&self, other: &Self) -> i64 {
+        other.days_since_epoch() - self.days_since_epoch()
+    }
+
+    // Number of days since 1970-01-01, based on the algorithm
+    // `days_from_civil` published by Howard Hinnant.
+    pub(crate) const fn days_since_epoch(&self) -> i64 {
+        let month = self.month as i64;
+        let year = if month <= 2 {
+            self.year as i64 - 1
+        } else {
+            self.year as i64
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2).div_euclid(5)
+            + self.day as i64
+            - 1;
+        let day_of_era = year_of_era * 365 + year_of_era.div_euclid(4)
+            - year_of_era.div_euclid(100)
+            + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+}
+
+impl fmt::Display for Date {
+    /// Formats `self` according to ISO 8601, i.e. as "YYYY-MM-DD".
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(form, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Returns `true` if `year` is a leap year.
+#[inline]
+pub(crate) const fn is_leap_year(year: u16) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Returns the number of days in the given year.
+#[inline]
+pub(crate) const fn days_in_year(year: u16) -> u16 {
+    if is_leap_year(year) {
+        366
+    } else {
+        365
+    }
+}
+
+/// Returns the number of days of the given month.
+pub(crate) const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_since_epoch() {
+        assert_eq!(Date::new(1970, 1, 1).days_since_epoch(), 0);
+        assert_eq!(Date::new(2000, 3, 1).days_since_epoch(), 11_017);
+        assert_eq!(Date::new(1969, 12, 31).days_since_epoch(), -1);
+        assert_eq!(Date::new(1, 1, 1).days_since_epoch(), -719_162);
+        assert_eq!(Date::new(9999, 12, 31).days_since_epoch(), 2_932_896);
+    }
+
+    #[test]
+    fn test_leap_years() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2023));
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2100, 2), 28);
+        assert_eq!(days_in_year(2024), 366);
+    }
+}
@@ -0,0 +1,181 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use fpdec::{Decimal, MulRounded};
+
+use crate::{
+    date::{days_in_year, Date},
+    AmountT, Money, Percent, Quantity,
+};
+
+/// Convention used to determine the fraction of a year between two dates.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Date, DayCount, Dec, Decimal};
+/// let start = Date::new(2024, 1, 15);
+/// let end = Date::new(2024, 7, 15);
+/// assert_eq!(DayCount::Act360.year_fraction(start, end), Dec!(182) / 360);
+/// assert_eq!(DayCount::Thirty360Us.year_fraction(start, end), Dec!(0.5));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DayCount {
+    /// Actual number of days divided by 360
+    Act360,
+    /// Actual number of days divided by 365
+    Act365Fixed,
+    /// 30/360 US (aka. Bond Basis): months are counted as 30 days, years
+    /// as 360 days, with the following adjustments:
+    /// * If start and end date are the last day of February, the end day is
+    ///   set to 30.
+    /// * If the start date is the last day of February, the start day is set
+    ///   to 30.
+    /// * If the end day is 31 and the (adjusted) start day is 30 or 31, the
+    ///   end day is set to 30.
+    /// * If the start day is 31, it is set to 30.
+    Thirty360Us,
+    /// 30E/360 (aka. Eurobond Basis): months are counted as 30 days, years
+    /// as 360 days; start or end day 31 is set to 30.
+    Thirty360Eu,
+    /// ACT/ACT ISDA: the days falling into leap years are divided by 366,
+    /// the other days by 365.
+    ActActIsda,
+}
+
+impl DayCount {
+    /// Returns the number of days between `start` and `end` according to
+    /// `self`.
+    ///
+    /// If `end` is before `start`, the result is the negated number of days
+    /// between `end` and `start`.
+    #[must_use]
+    pub fn day_count(&self, start: Date, end: Date) -> i64 {
+        if end < start {
+            return -self.day_count(end, start);
+        }
+        match self {
+            Self::Act360 | Self::Act365Fixed | Self::ActActIsda => {
+                start.days_until(&end)
+            }
+            Self::Thirty360Us => thirty_360_us(start, end),
+            Self::Thirty360Eu => thirty_360_eu(start, end),
+        }
+    }
+
+    /// Returns the fraction of a year between `start` and `end` according to
+    /// `self`.
+    ///
+    /// If `end` is before `start`, the result is the negated fraction of a
+    /// year between `end` and `start`.
+    #[must_use]
+    pub fn year_fraction(&self, start: Date, end: Date) -> AmountT {
+        match self {
+            Self::Act360 | Self::Thirty360Us | Self::Thirty360Eu => {
+                Decimal::from(self.day_count(start, end)) / 360
+            }
+            Self::Act365Fixed => {
+                Decimal::from(self.day_count(start, end)) / 365
+            }
+            Self::ActActIsda => {
+                if end < start {
+                    -act_act_isda(end, start)
+                } else {
+                    act_act_isda(start, end)
+                }
+            }
+        }
+    }
+}
+
+fn thirty_360(start: (u16, u8, u8), end: (u16, u8, u8)) -> i64 {
+    360 * (i64::from(end.0) - i64::from(start.0))
+        + 30 * (i64::from(end.1) - i64::from(start.1))
+        + (i64::from(end.2) - i64::from(start.2))
+}
+
+fn thirty_360_us(start: Date, end: Date) -> i64 {
+    let start_is_feb_eom = start.month() == 2 && start.is_last_day_of_month();
+    let end_is_feb_eom = end.month() == 2 && end.is_last_day_of_month();
+    let mut d1 = start.day();
+    let mut d2 = end.day();
+    if start_is_feb_eom && end_is_feb_eom {
+        d2 = 30;
+    }
+    if start_is_feb_eom {
+        d1 = 30;
+    }
+    if d2 == 31 && d1 >= 30 {
+        d2 = 30;
+    }
+    if d1 == 31 {
+        d1 = 30;
+    }
+    thirty_360(
+        (start.year(), start.month(), d1),
+        (end.year(), end.month(), d2),
+    )
+}
+
+fn thirty_360_eu(start: Date, end: Date) -> i64 {
+    thirty_360(
+        (start.year(), start.month(), start.day().min(30)),
+        (end.year(), end.month(), end.day().min(30)),
+    )
+}
+
+// Year fraction according to ACT/ACT ISDA, `start` <= `end` assumed.
+fn act_act_isda(start: Date, end: Date) -> AmountT {
+    if start.year() == end.year() {
+        return Decimal::from(start.days_until(&end))
+            / days_in_year(start.year());
+    }
+    let start_year_end = Date::new(start.year() + 1, 1, 1);
+    let end_year_start = Date::new(end.year(), 1, 1);
+    Decimal::from(start.days_until(&start_year_end))
+        / days_in_year(start.year())
+        + Decimal::from(end.year() - start.year() - 1)
+        + Decimal::from(end_year_start.days_until(&end))
+            / days_in_year(end.year())
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns the simple interest accrued on `self` at the given annual
+    /// rate between `start` and `end`, using the given day count convention,
+    /// rounded to the minor unit of `self.unit()`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Date, DayCount, Dec, Decimal, Percent, EUR};
+    /// let m = Dec!(100000) * EUR;
+    /// let interest = m.accrue(
+    ///     Percent::new(Dec!(3.5)),
+    ///     Date::new(2024, 1, 15),
+    ///     Date::new(2024, 4, 15),
+    ///     DayCount::Act360,
+    /// );
+    /// assert_eq!(interest, Dec!(884.72) * EUR);
+    /// ```
+    #[must_use]
+    pub fn accrue(
+        &self,
+        rate: Percent,
+        start: Date,
+        end: Date,
+        day_count: DayCount,
+    ) -> Self {
+        let factor = rate.as_fraction() * day_count.year_fraction(start, end);
+        Self::new(
+            self.amount.mul_rounded(factor, self.unit.minor_unit()),
+            self.unit,
+        )
+    }
+}
@@ -0,0 +1,249 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::string::String;
+use core::fmt;
+
+use fpdec::ParseDecimalError;
+
+use crate::{AmountT, Currency, CurrencyKeyError, Money};
+
+/// Error type covering all failures of operations in this crate.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Money, MoneyError};
+/// match "17.50 ???".parse::<Money>() {
+///     Err(MoneyError::UnknownCurrency { symbol }) => {
+///         assert_eq!(symbol, "???")
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MoneyError {
+    /// A currency symbol is invalid or a currency could not be registered,
+    /// changed or removed (including duplicate registrations, see
+    /// `CurrencyKeyError::AlreadyRegistered`).
+    CurrencyKey(CurrencyKeyError),
+    /// The operands of an operation have different currencies.
+    CurrencyMismatch {
+        /// The currency expected.
+        expected: Currency,
+        /// The currency found.
+        found: Currency,
+    },
+    /// An aggregation has been applied to an empty sequence of values.
+    Empty,
+    /// The string to be parsed is not of the form `<amount> <symbol>`.
+    InvalidFormat {
+        /// The string given.
+        input: String,
+    },
+    /// The amount part of the string to be parsed is not a valid decimal
+    /// literal.
+    InvalidAmount(ParseDecimalError),
+    /// There is no currency with the given symbol.
+    UnknownCurrency {
+        /// The symbol given.
+        symbol: String,
+    },
+    /// The unit currency and term currency of an exchange rate, or the
+    /// currency and base currency of a currency position, are identical.
+    IdenticalRateCurrencies(Currency),
+    /// The unit multiple of an exchange rate is 0 or, after adjustment, >
+    /// 1_000_000_000.
+    InvalidUnitMultiple(u32),
+    /// The term amount of an exchange rate is <= 0.
+    InvalidTermAmount(AmountT),
+    /// The given year, month and day do not denote a valid date.
+    InvalidDate {
+        /// The year given.
+        year: u16,
+        /// The month given.
+        month: u8,
+        /// The day given.
+        day: u8,
+    },
+    /// An iterative calculation did not find a solution.
+    NoConvergence,
+    /// The result of a calculation exceeds the range of `Decimal`.
+    Overflow,
+    /// The divisor of a division is zero.
+    DivisionByZero,
+    /// The lower bound of a range is greater than its upper bound.
+    InvalidRange {
+        /// The lower bound given.
+        min: Money,
+        /// The upper bound given.
+        max: Money,
+    },
+    /// An account with the given code already exists in a ledger.
+    DuplicateAccount {
+        /// The code given.
+        code: String,
+    },
+    /// There is no account with the given code in a ledger.
+    UnknownAccount {
+        /// The code given.
+        code: String,
+    },
+    /// A journal entry has no postings.
+    EmptyEntry,
+    /// The amount of a posting is negative.
+    NegativeAmount {
+        /// The code of the account.
+        account: String,
+        /// The amount given.
+        amount: Money,
+    },
+    /// The sums of the debits and credits of a journal entry in one currency
+    /// are not equal.
+    UnbalancedEntry {
+        /// The sum of the debits.
+        debit: Money,
+        /// The sum of the credits.
+        credit: Money,
+    },
+    /// An amount which must be positive is zero or negative.
+    NotPositive(Money),
+    /// The fees charged exceed the amount they are applied to.
+    FeeExceedsAmount {
+        /// The amount the fees are applied to.
+        amount: Money,
+        /// The sum of the fees.
+        fee: Money,
+    },
+    /// The amount to be removed exceeds the amount available.
+    InsufficientQuantity {
+        /// The amount available.
+        available: Money,
+        /// The amount requested.
+        requested: Money,
+    },
+    /// The number of fractional digits of a price is less than the minor
+    /// unit of its currency or greater than the maximum number of fractional
+    /// digits supported by `Decimal`.
+    InvalidPrecision {
+        /// The currency given.
+        currency: Currency,
+        /// The precision given.
+        precision: u8,
+    },
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CurrencyKey(err) => fmt::Display::fmt(err, form),
+            Self::CurrencyMismatch { expected, found } => {
+                write!(form, "Expected currency '{expected}', got '{found}'.")
+            }
+            Self::Empty => form.write_str("No values given."),
+            Self::InvalidFormat { input } => write!(
+                form,
+                "'{input}' is not of the form '<amount> <symbol>'."
+            ),
+            Self::InvalidAmount(err) => {
+                write!(form, "Invalid amount: {err}")
+            }
+            Self::UnknownCurrency { symbol } => {
+                write!(form, "Unknown currency '{symbol}'.")
+            }
+            Self::IdenticalRateCurrencies(_) => {
+                form.write_str("The currencies given must not be identical.")
+            }
+            Self::InvalidUnitMultiple(0) => {
+                form.write_str("Unit multiple must be >= 1.")
+            }
+            Self::InvalidUnitMultiple(_) => form.write_str(
+                "Adjusted unit multiple must be <= 1_000_000_000.",
+            ),
+            Self::InvalidTermAmount(_) => {
+                form.write_str("Term amount must be > 0.")
+            }
+            Self::InvalidDate { year, month, day } => {
+                write!(form, "Invalid date: {year:04}-{month:02}-{day:02}.")
+            }
+            Self::NoConvergence => {
+                form.write_str("Calculation did not find a solution.")
+            }
+            Self::Overflow => {
+                form.write_str("Result exceeds the range of 'Decimal'.")
+            }
+            Self::DivisionByZero => form.write_str("Division by zero."),
+            Self::InvalidRange { min, max } => {
+                write!(form, "Invalid range: {min} > {max}.")
+            }
+            Self::DuplicateAccount { code } => {
+                write!(form, "Account '{code}' already exists.")
+            }
+            Self::UnknownAccount { code } => {
+                write!(form, "Unknown account '{code}'.")
+            }
+            Self::EmptyEntry => {
+                form.write_str("Journal entry has no postings.")
+            }
+            Self::NegativeAmount { account, amount } => write!(
+                form,
+                "Negative amount {amount} posted to account '{account}'."
+            ),
+            Self::UnbalancedEntry { debit, credit } => write!(
+                form,
+                "Journal entry is unbalanced: debit {debit}, credit {credit}."
+            ),
+            Self::NotPositive(amount) => {
+                write!(form, "Amount must be > 0, got {amount}.")
+            }
+            Self::FeeExceedsAmount { amount, fee } => {
+                write!(form, "Fee {fee} exceeds amount {amount}.")
+            }
+            Self::InsufficientQuantity {
+                available,
+                requested,
+            } => write!(
+                form,
+                "Requested {requested}, but only {available} available."
+            ),
+            Self::InvalidPrecision {
+                currency,
+                precision,
+            } => write!(
+                form,
+                "Precision {precision} is out of range for currency \
+                 '{currency}'."
+            ),
+        }
+    }
+}
+
+impl core::error::Error for MoneyError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::CurrencyKey(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<CurrencyKeyError> for MoneyError {
+    #[inline]
+    fn from(err: CurrencyKeyError) -> Self {
+        Self::CurrencyKey(err)
+    }
+}
+
+impl From<ParseDecimalError> for MoneyError {
+    #[inline]
+    fn from(err: ParseDecimalError) -> Self {
+        Self::InvalidAmount(err)
+    }
+}
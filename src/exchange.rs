@@ -15,7 +15,11 @@ use core::{
 use fpdec::{Decimal, DivRounded};
 use fpdec_core::ten_pow;
 
-use crate::{AmountT, Currency, Money, Quantity};
+use crate::{AmountT, Currency, Money, MoneyError, Quantity};
+
+// Max number of minor units an estimated source amount for a target is
+// corrected by
+const MAX_CORRECTION: u32 = 8;
 
 /// Basic representation of a conversion factor between two currencies.
 ///
@@ -62,29 +66,58 @@ impl ExchangeRate {
         term_currency: Currency,
         term_amount: AmountT,
     ) -> Self {
-        assert!(
-            unit_currency != term_currency,
-            "The currencies given must not be identical."
-        );
-        assert_ne!(unit_multiple, 0, "Unit multiple must be >= 1.");
-        assert!(term_amount.is_positive(), "Term amount must be > 0.");
+        match Self::try_new(
+            unit_currency,
+            unit_multiple,
+            term_currency,
+            term_amount,
+        ) {
+            Ok(rate) => rate,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Returns a new instance of `ExchangeRate`, or an error if the given
+    /// arguments do not define a valid exchange rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * `unit_currency` is equal to `term_currency`.
+    /// * `unit_multiple` == 0
+    /// * `term_amount` <= 0
+    /// * adjusted unit multiple > 1_000_000_000
+    pub fn try_new(
+        unit_currency: Currency,
+        unit_multiple: u32,
+        term_currency: Currency,
+        term_amount: AmountT,
+    ) -> Result<Self, MoneyError> {
+        if unit_currency == term_currency {
+            return Err(MoneyError::IdenticalRateCurrencies(unit_currency));
+        }
+        if unit_multiple == 0 {
+            return Err(MoneyError::InvalidUnitMultiple(unit_multiple));
+        }
+        if !term_amount.is_positive() {
+            return Err(MoneyError::InvalidTermAmount(term_amount));
+        }
         // adjust unit_multiple and term_amount so that unit_multiple is a
         // power to 10 and term_amount.magnitude >= -1
         let magn = Decimal::from(unit_multiple).magnitude()
             - min(0, term_amount.magnitude() + 1);
-        assert!(
-            (0..=9).contains(&magn),
-            "Adjusted unit multiple must be <= 1_000_000_000."
-        );
+        if !(0..=9).contains(&magn) {
+            return Err(MoneyError::InvalidUnitMultiple(unit_multiple));
+        }
         #[allow(clippy::cast_possible_truncation)]
         #[allow(clippy::cast_sign_loss)]
-        Self {
+        Ok(Self {
             unit_currency,
             unit_multiple: ten_pow(magn as u8) as u32,
             term_currency,
             term_amount: (term_amount * ten_pow(magn as u8))
                 .div_rounded(unit_multiple, 6),
-        }
+        })
     }
 
     /// Currency to be converted from, aka base currency
@@ -152,6 +185,169 @@ impl ExchangeRate {
             self.inverse_rate(),
         )
     }
+
+    /// Returns the source amount to be converted by `self` in order to
+    /// obtain `target`, taking into account that the result of the
+    /// conversion is rounded to the minor unit of the target currency.
+    ///
+    /// The currency of the result is the other currency of `self`, i.e. the
+    /// unit currency if `target` is in term currency and vice versa. Because
+    /// of rounding, there may be no source amount converting exactly to
+    /// `target`, or there may be several. Depending on `rounding`, the
+    /// function returns
+    /// * `TargetRounding::AtLeast`: the smallest source amount whose
+    ///   conversion yields at least `target`,
+    /// * `TargetRounding::AtMost`: the largest source amount whose
+    ///   conversion yields at most `target`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * the currency of `target` is neither the unit nor the term currency
+    ///   of `self`
+    /// * no source amount meeting `target` could be determined
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, ExchangeRate, TargetRounding, JPY, USD};
+    /// let rate = ExchangeRate::new(USD, 1, JPY, Dec!(151.237));
+    /// let target = Dec!(10002) * JPY;
+    /// let source = rate.source_for_target(target, TargetRounding::AtLeast)?;
+    /// assert_eq!(source, Dec!(66.14) * USD);
+    /// assert_eq!(source * rate, Dec!(10003) * JPY);
+    /// let source = rate.source_for_target(target, TargetRounding::AtMost)?;
+    /// assert_eq!(source, Dec!(66.13) * USD);
+    /// assert_eq!(source * rate, Dec!(10001) * JPY);
+    /// # Ok::<(), moneta::MoneyError>(())
+    /// ```
+    pub fn source_for_target(
+        &self,
+        target: Money,
+        rounding: TargetRounding,
+    ) -> Result<Money, MoneyError> {
+        let to_term = if target.unit() == self.term_currency {
+            true
+        } else if target.unit() == self.unit_currency {
+            false
+        } else {
+            return Err(MoneyError::CurrencyMismatch {
+                expected: self.term_currency,
+                found: target.unit(),
+            });
+        };
+        let convert = |source: Money| {
+            if to_term {
+                source * *self
+            } else {
+                source / *self
+            }
+        };
+        let (source_currency, rate) = if to_term {
+            (self.unit_currency, self.inverse_rate())
+        } else {
+            (self.term_currency, self.rate())
+        };
+        // Estimate the source amount from the bounds of the interval of
+        // amounts being rounded to `target`, then correct it in steps of the
+        // minor unit of the source currency, in order to compensate the
+        // effects of rounding ties and of limited precision.
+        let minor_unit = source_currency.minor_unit();
+        let scale = ten_pow(minor_unit);
+        let step =
+            Money::new(Decimal::new_raw(1, minor_unit), source_currency);
+        let half = Decimal::new_raw(5, target.unit().minor_unit() + 1);
+        let source = match rounding {
+            TargetRounding::AtLeast => {
+                let bound = (target.amount() - half) * rate;
+                Money::new((bound * scale).ceil() / scale, source_currency)
+            }
+            TargetRounding::AtMost => {
+                let bound = (target.amount() + half) * rate;
+                Money::new((bound * scale).floor() / scale, source_currency)
+            }
+        };
+        match rounding {
+            TargetRounding::AtLeast => {
+                correct_by_steps(source, step, |source| {
+                    convert(source) >= target
+                })
+            }
+            TargetRounding::AtMost => {
+                correct_by_steps(source, -step, |source| {
+                    convert(source) <= target
+                })
+            }
+        }
+    }
+
+    // Returns `self` with the term amount multiplied by `factor`, without
+    // rounding it like `ExchangeRate::new`.
+    pub(crate) fn scaled(&self, factor: AmountT) -> Self {
+        Self {
+            term_amount: self.term_amount * factor,
+            ..*self
+        }
+    }
+
+    // Returns the equivalent of `money` in the other currency of `self`,
+    // i.e. `money * self` if the currency of `money` is the unit currency of
+    // `self` and `money / self` if it is the term currency.
+    pub(crate) fn try_convert(
+        &self,
+        money: Money,
+    ) -> Result<Money, MoneyError> {
+        if money.unit() == self.unit_currency {
+            Ok(money * *self)
+        } else if money.unit() == self.term_currency {
+            Ok(money / *self)
+        } else {
+            Err(MoneyError::CurrencyMismatch {
+                expected: self.unit_currency,
+                found: money.unit(),
+            })
+        }
+    }
+}
+
+// Returns the estimated amount `source` corrected in steps of `step`: it is
+// moved by `step` until it meets `bound`, then moved back as long as the
+// amount still meets `bound`, by at most `MAX_CORRECTION` steps each.
+pub(crate) fn correct_by_steps<F>(
+    mut source: Money,
+    step: Money,
+    bound: F,
+) -> Result<Money, MoneyError>
+where
+    F: Fn(Money) -> bool,
+{
+    for _ in 0..MAX_CORRECTION {
+        if bound(source) {
+            break;
+        }
+        source += step;
+    }
+    if !bound(source) {
+        return Err(MoneyError::NoConvergence);
+    }
+    for _ in 0..MAX_CORRECTION {
+        if !bound(source - step) {
+            break;
+        }
+        source -= step;
+    }
+    Ok(source)
+}
+
+/// Bound to be met by the conversion of the source amount calculated by
+/// `ExchangeRate::source_for_target`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum TargetRounding {
+    /// The conversion yields at least the target amount.
+    #[default]
+    AtLeast,
+    /// The conversion yields at most the target amount.
+    AtMost,
 }
 
 impl Mul<Money> for ExchangeRate {
@@ -297,6 +493,103 @@ impl Div<Self> for ExchangeRate {
     }
 }
 
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns the realized exchange gain (positive) or loss (negative) in
+    /// functional currency, resulting from settling `self` at
+    /// `settlement_rate` after it had been booked at `booking_rate`.
+    ///
+    /// `self` is an open item in foreign currency, i.e. positive for a
+    /// receivable and negative for a payable. The functional currency is the
+    /// other currency of the given rates; each rate may be quoted in either
+    /// direction, i.e. `self` is multiplied by the rate if its currency is
+    /// the unit currency of the rate, and divided by the rate if its currency
+    /// is the term currency.
+    ///
+    /// Both functional amounts are rounded to the minor unit of the
+    /// functional currency before the difference is taken, so that the result
+    /// is exactly the amount needed to adjust the booked value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * the currency of `self` is neither the unit nor the term currency of
+    ///   one of the rates
+    /// * the rates convert to different functional currencies
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, ExchangeRate, EUR, USD};
+    /// let receivable = Dec!(1000) * USD;
+    /// let booking_rate = ExchangeRate::new(USD, 1, EUR, Dec!(0.9132));
+    /// let settlement_rate = ExchangeRate::new(EUR, 1, USD, Dec!(1.0725));
+    /// assert_eq!(
+    ///     receivable.realized_fx_gain_loss(booking_rate, settlement_rate)?,
+    ///     Dec!(19.20) * EUR
+    /// );
+    /// # Ok::<(), moneta::MoneyError>(())
+    /// ```
+    pub fn realized_fx_gain_loss(
+        &self,
+        booking_rate: ExchangeRate,
+        settlement_rate: ExchangeRate,
+    ) -> Result<Self, MoneyError> {
+        self.fx_gain_loss(booking_rate, settlement_rate)
+    }
+
+    /// Returns the unrealized exchange gain (positive) or loss (negative) in
+    /// functional currency, resulting from revaluing `self` at
+    /// `revaluation_rate` after it had been booked (or last revalued) at
+    /// `booking_rate`.
+    ///
+    /// See `Money::realized_fx_gain_loss` for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * the currency of `self` is neither the unit nor the term currency of
+    ///   one of the rates
+    /// * the rates convert to different functional currencies
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, ExchangeRate, EUR, USD};
+    /// let payable = Dec!(-2500) * USD;
+    /// let booking_rate = ExchangeRate::new(USD, 1, EUR, Dec!(0.9132));
+    /// let closing_rate = ExchangeRate::new(USD, 1, EUR, Dec!(0.9251));
+    /// assert_eq!(
+    ///     payable.unrealized_fx_gain_loss(booking_rate, closing_rate)?,
+    ///     Dec!(-29.75) * EUR
+    /// );
+    /// # Ok::<(), moneta::MoneyError>(())
+    /// ```
+    pub fn unrealized_fx_gain_loss(
+        &self,
+        booking_rate: ExchangeRate,
+        revaluation_rate: ExchangeRate,
+    ) -> Result<Self, MoneyError> {
+        self.fx_gain_loss(booking_rate, revaluation_rate)
+    }
+
+    fn fx_gain_loss(
+        &self,
+        booking_rate: ExchangeRate,
+        current_rate: ExchangeRate,
+    ) -> Result<Self, MoneyError> {
+        let booked = booking_rate.try_convert(*self)?;
+        let current = current_rate.try_convert(*self)?;
+        if booked.unit() != current.unit() {
+            return Err(MoneyError::CurrencyMismatch {
+                expected: booked.unit(),
+                found: current.unit(),
+            });
+        }
+        Ok(current - booked)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,205 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use fpdec::Decimal;
+
+use crate::{math::powi, AmountT, Money, Percent};
+
+/// Frequency of compounding interest.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Compounding {
+    /// No compounding, i.e. simple interest
+    Simple,
+    /// Once a year
+    Annually,
+    /// Twice a year
+    SemiAnnually,
+    /// Four times a year
+    Quarterly,
+    /// Twelve times a year
+    Monthly,
+    /// 365 times a year
+    Daily,
+    /// The given number of times a year
+    PerYear(u16),
+}
+
+impl Compounding {
+    /// Returns the number of compounding periods per year, or `None` in case
+    /// of simple interest.
+    #[must_use]
+    pub const fn periods_per_year(&self) -> Option<u16> {
+        match self {
+            Self::Simple => None,
+            Self::Annually => Some(1),
+            Self::SemiAnnually => Some(2),
+            Self::Quarterly => Some(4),
+            Self::Monthly => Some(12),
+            Self::Daily => Some(365),
+            Self::PerYear(n) => Some(*n),
+        }
+    }
+}
+
+/// Strategy used to round the results of interest calculations.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum InterestRounding {
+    /// All calculations are done with full precision; only the final result
+    /// is rounded to the minor unit of the currency.
+    #[default]
+    Final,
+    /// The interest accrued in each compounding period is rounded to the
+    /// minor unit of the currency before being added to the balance, as it
+    /// is done when interest is credited to an account.
+    PerPeriod,
+}
+
+/// Annual interest rate together with the frequency of compounding and the
+/// rounding strategy to be applied.
+///
+/// For a period not being a whole multiple of the compounding period, the
+/// interest for the remaining fraction of a compounding period is
+/// calculated as simple interest.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Compounding, Dec, Decimal, InterestRate, Percent, EUR};
+/// let rate = InterestRate::new(Percent::new(Dec!(5)), Compounding::Monthly);
+/// let principal = Dec!(10000) * EUR;
+/// assert_eq!(rate.future_value(principal, Dec!(1)), Dec!(10511.62) * EUR);
+/// assert_eq!(rate.interest(principal, Dec!(1)), Dec!(511.62) * EUR);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct InterestRate {
+    annual_rate: Percent,
+    compounding: Compounding,
+    rounding: InterestRounding,
+}
+
+impl InterestRate {
+    /// Returns a new instance of `InterestRate`, using
+    /// `InterestRounding::Final`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `compounding` is `Compounding::PerYear(0)`
+    #[must_use]
+    pub const fn new(annual_rate: Percent, compounding: Compounding) -> Self {
+        assert!(
+            !matches!(compounding, Compounding::PerYear(0)),
+            "Number of compounding periods must be >= 1."
+        );
+        Self {
+            annual_rate,
+            compounding,
+            rounding: InterestRounding::Final,
+        }
+    }
+
+    /// Returns a copy of `self` using the given rounding strategy.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_rounding(self, rounding: InterestRounding) -> Self {
+        Self { rounding, ..self }
+    }
+
+    /// The annual interest rate
+    #[inline(always)]
+    #[must_use]
+    pub const fn annual_rate(&self) -> Percent {
+        self.annual_rate
+    }
+
+    /// The frequency of compounding
+    #[inline(always)]
+    #[must_use]
+    pub const fn compounding(&self) -> Compounding {
+        self.compounding
+    }
+
+    /// The rounding strategy
+    #[inline(always)]
+    #[must_use]
+    pub const fn rounding(&self) -> InterestRounding {
+        self.rounding
+    }
+
+    /// Returns the factor by which an amount grows over the given number of
+    /// years, calculated with full precision.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `years` < 0
+    /// * the number of compounding periods in `years` exceeds `u64::MAX`
+    /// * the result exceeds the range of `Decimal`
+    #[must_use]
+    pub fn growth_factor(&self, years: AmountT) -> AmountT {
+        assert!(!years.is_negative(), "Period must not be negative.");
+        let rate = self.annual_rate.as_fraction();
+        match self.compounding.periods_per_year() {
+            None => Decimal::ONE + rate * years,
+            Some(m) => {
+                let (n_periods, stub) = split_periods(years, m);
+                let period_rate = rate / Decimal::from(m);
+                powi(Decimal::ONE + period_rate, n_periods)
+                    * (Decimal::ONE + period_rate * stub)
+            }
+        }
+    }
+
+    /// Returns the value of `principal` after the given number of years,
+    /// rounded to the minor unit of its currency.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `years` < 0
+    /// * the number of compounding periods in `years` exceeds `u64::MAX`
+    /// * the result exceeds the range of `Decimal`
+    #[must_use]
+    pub fn future_value(&self, principal: Money, years: AmountT) -> Money {
+        match (self.rounding, self.compounding.periods_per_year()) {
+            (InterestRounding::PerPeriod, Some(m)) => {
+                assert!(!years.is_negative(), "Period must not be negative.");
+                let (n_periods, stub) = split_periods(years, m);
+                let period_rate =
+                    self.annual_rate.as_fraction() / Decimal::from(m);
+                let mut balance = principal;
+                for _ in 0..n_periods {
+                    balance += balance * period_rate;
+                }
+                balance + balance * (period_rate * stub)
+            }
+            _ => principal * self.growth_factor(years),
+        }
+    }
+
+    /// Returns the interest accrued on `principal` over the given number of
+    /// years, rounded to the minor unit of its currency.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `years` < 0
+    /// * the number of compounding periods in `years` exceeds `u64::MAX`
+    /// * the result exceeds the range of `Decimal`
+    #[must_use]
+    pub fn interest(&self, principal: Money, years: AmountT) -> Money {
+        self.future_value(principal, years) - principal
+    }
+}
+
+// Splits the given number of years into the number of whole compounding
+// periods and the remaining fraction of a period.
+// Panics if the number of whole periods exceeds `u64::MAX`.
+fn split_periods(years: AmountT, periods_per_year: u16) -> (u64, AmountT) {
+    let periods = years * Decimal::from(periods_per_year);
+    let n_periods = u64::try_from(periods.trunc())
+        .expect("Number of compounding periods exceeds limit.");
+    (n_periods, periods.fract())
+}
@@ -0,0 +1,394 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::{vec, vec::Vec};
+use core::slice;
+
+use crate::{
+    aggregate::check_currency, tax::distribute, AmountT, Currency, LineItem,
+    Money, MoneyError, Percent, Quantity, TaxAmount, TaxBreakdown,
+    TaxRounding,
+};
+
+/// Line of an invoice: a `LineItem`, i.e. a quantity of goods or services
+/// and its unit price, and the tax rate applicable.
+///
+/// A line is either a regular line created by `InvoiceLine::new`, or a
+/// credit line created by `InvoiceLine::credit`, e.g. for returned goods,
+/// whose net and tax amounts are negative.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceLine {
+    item: LineItem,
+    tax_rate: Percent,
+    credit: bool,
+}
+
+impl InvoiceLine {
+    /// Returns a new invoice line.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `quantity` < 0
+    /// * `unit_price` < 0
+    #[must_use]
+    pub fn new(
+        description: &str,
+        quantity: AmountT,
+        unit_price: Money,
+        tax_rate: Percent,
+    ) -> Self {
+        Self {
+            item: LineItem::new(description, quantity, unit_price),
+            tax_rate,
+            credit: false,
+        }
+    }
+
+    /// Returns a new credit line, crediting `quantity` units at
+    /// `unit_price`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `quantity` < 0
+    /// * `unit_price` < 0
+    #[must_use]
+    pub fn credit(
+        description: &str,
+        quantity: AmountT,
+        unit_price: Money,
+        tax_rate: Percent,
+    ) -> Self {
+        Self {
+            credit: true,
+            ..Self::new(description, quantity, unit_price, tax_rate)
+        }
+    }
+
+    /// The line item invoiced or credited
+    #[inline(always)]
+    #[must_use]
+    pub const fn item(&self) -> &LineItem {
+        &self.item
+    }
+
+    /// The description of the goods or services
+    #[inline(always)]
+    #[must_use]
+    pub fn description(&self) -> &str {
+        self.item.article()
+    }
+
+    /// The number of units, negative for a credit line
+    #[inline]
+    #[must_use]
+    pub fn quantity(&self) -> AmountT {
+        if self.credit {
+            -self.item.quantity()
+        } else {
+            self.item.quantity()
+        }
+    }
+
+    /// The price per unit
+    #[inline(always)]
+    #[must_use]
+    pub const fn unit_price(&self) -> Money {
+        self.item.unit_price()
+    }
+
+    /// The tax rate applicable
+    #[inline(always)]
+    #[must_use]
+    pub const fn tax_rate(&self) -> Percent {
+        self.tax_rate
+    }
+
+    /// Returns `true` if `self` is a credit line.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_credit(&self) -> bool {
+        self.credit
+    }
+
+    // The net amount of `self`, not rounded
+    fn exact_net(&self) -> AmountT {
+        self.unit_price().amount * self.quantity()
+    }
+}
+
+/// Invoice, i.e. a list of lines in a common currency, and the calculation
+/// of its totals.
+///
+/// How the amounts are rounded depends on the invoice's `TaxRounding`:
+/// * `TaxRounding::PerLine`: the net amount of each line (quantity times
+///   unit price) and the tax on it are rounded to the minor unit of the
+///   currency; the subtotals and totals are the sums of these amounts.
+/// * `TaxRounding::PerInvoice`: the net subtotal per tax rate is the exact
+///   sum of the lines' quantity times unit price, rounded once; the tax per
+///   rate is calculated on this subtotal and rounded once. The rounding
+///   differences are distributed to the lines, so that the net and tax
+///   amounts of the lines add up to the subtotals.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{
+/// #     Dec, Decimal, Invoice, InvoiceLine, Percent, TaxRounding, EUR,
+/// # };
+/// let vat = Percent::new(Dec!(19));
+/// let cable = InvoiceLine::new("Cable", Dec!(3.33), Dec!(0.3) * EUR, vat);
+/// let screws = InvoiceLine::new("Screws", Dec!(7.5), Dec!(0.13) * EUR, vat);
+/// let mut invoice = Invoice::new(EUR, TaxRounding::PerInvoice);
+/// invoice.add_line(cable.clone())?;
+/// invoice.add_line(screws.clone())?;
+/// let totals = invoice.totals();
+/// assert_eq!(totals.net(), Dec!(1.97) * EUR);
+/// assert_eq!(totals.tax(), Dec!(0.37) * EUR);
+/// assert_eq!(totals.gross(), Dec!(2.34) * EUR);
+/// assert_eq!(totals.lines()[0].net(), Dec!(1.00) * EUR);
+/// assert_eq!(totals.lines()[1].net(), Dec!(0.97) * EUR);
+/// let invoice = Invoice::new(EUR, TaxRounding::PerLine)
+///     .with_line(cable)
+///     .with_line(screws);
+/// let totals = invoice.totals();
+/// assert_eq!(totals.net(), Dec!(1.98) * EUR);
+/// assert_eq!(totals.tax(), Dec!(0.38) * EUR);
+/// assert_eq!(totals.gross(), Dec!(2.36) * EUR);
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invoice {
+    currency: Currency,
+    rounding: TaxRounding,
+    lines: Vec<InvoiceLine>,
+}
+
+impl Invoice {
+    /// Returns a new invoice in `currency` without lines, with amounts
+    /// rounded according to `rounding`.
+    #[must_use]
+    pub const fn new(currency: Currency, rounding: TaxRounding) -> Self {
+        Self {
+            currency,
+            rounding,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Appends `line` to `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the currency of the unit price of `line` differs
+    /// from the currency of `self`.
+    pub fn add_line(&mut self, line: InvoiceLine) -> Result<(), MoneyError> {
+        check_currency(self.currency, &line.unit_price())?;
+        self.lines.push(line);
+        Ok(())
+    }
+
+    /// Returns `self` with `line` appended.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * the currency of the unit price of `line` differs from the currency
+    ///   of `self`
+    #[must_use]
+    pub fn with_line(mut self, line: InvoiceLine) -> Self {
+        if let Err(err) = self.add_line(line) {
+            panic!("{err}");
+        }
+        self
+    }
+
+    /// The currency of all amounts
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The method used to round the amounts
+    #[inline(always)]
+    #[must_use]
+    pub const fn rounding(&self) -> TaxRounding {
+        self.rounding
+    }
+
+    /// The lines of `self`
+    #[inline(always)]
+    #[must_use]
+    pub fn lines(&self) -> &[InvoiceLine] {
+        &self.lines
+    }
+
+    /// Returns the net and tax amounts of the lines of `self` and the
+    /// subtotals per tax rate and totals.
+    #[must_use]
+    pub fn totals(&self) -> InvoiceTotals {
+        let zero = Money::zero(self.currency);
+        let mut lines = vec![
+            LineTotal {
+                net: zero,
+                tax: zero,
+            };
+            self.lines.len()
+        ];
+        match self.rounding {
+            TaxRounding::PerLine => {
+                for (total, line) in lines.iter_mut().zip(&self.lines) {
+                    total.net = line.unit_price() * line.quantity();
+                    total.tax = total.net * line.tax_rate;
+                }
+            }
+            TaxRounding::PerInvoice => {
+                for idxs in self.groups() {
+                    let exact: Vec<AmountT> = idxs
+                        .iter()
+                        .map(|&idx| self.lines[idx].exact_net())
+                        .collect();
+                    let net = Money::new(
+                        exact.iter().copied().sum(),
+                        self.currency,
+                    );
+                    for (&idx, amount) in
+                        idxs.iter().zip(distribute(net, &exact))
+                    {
+                        lines[idx].net = amount;
+                    }
+                }
+            }
+        }
+        let breakdown = TaxBreakdown::from_lines(
+            lines.iter().zip(&self.lines).map(|(total, line)| {
+                (total.net, slice::from_ref(&line.tax_rate))
+            }),
+            self.rounding,
+        )
+        .unwrap_or_else(|_| zero.add_tax(&[]));
+        if self.rounding == TaxRounding::PerInvoice {
+            for (idxs, tax) in self.groups().iter().zip(breakdown.taxes()) {
+                let exact: Vec<AmountT> = idxs
+                    .iter()
+                    .map(|&idx| {
+                        lines[idx].net.amount * tax.rate().as_fraction()
+                    })
+                    .collect();
+                for (&idx, amount) in
+                    idxs.iter().zip(distribute(tax.amount(), &exact))
+                {
+                    lines[idx].tax = amount;
+                }
+            }
+        }
+        InvoiceTotals { lines, breakdown }
+    }
+
+    // Returns the indices of the lines of `self`, grouped by tax rate in
+    // order of first occurrence.
+    fn groups(&self) -> Vec<Vec<usize>> {
+        let mut rates: Vec<Percent> = Vec::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for (idx, line) in self.lines.iter().enumerate() {
+            if let Some(pos) = rates.iter().position(|r| *r == line.tax_rate)
+            {
+                groups[pos].push(idx);
+            } else {
+                rates.push(line.tax_rate);
+                groups.push(vec![idx]);
+            }
+        }
+        groups
+    }
+}
+
+/// Net and tax amount of an invoice line.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LineTotal {
+    net: Money,
+    tax: Money,
+}
+
+impl LineTotal {
+    /// The net amount
+    #[inline(always)]
+    #[must_use]
+    pub const fn net(&self) -> Money {
+        self.net
+    }
+
+    /// The tax amount
+    #[inline(always)]
+    #[must_use]
+    pub const fn tax(&self) -> Money {
+        self.tax
+    }
+
+    /// The gross amount, i.e. net amount plus tax amount
+    #[inline]
+    #[must_use]
+    pub fn gross(&self) -> Money {
+        self.net + self.tax
+    }
+}
+
+/// Amounts calculated for an `Invoice`.
+///
+/// It is guaranteed that the net and tax amounts of the lines add up to the
+/// subtotals per tax rate, and that these add up to the totals.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceTotals {
+    lines: Vec<LineTotal>,
+    breakdown: TaxBreakdown,
+}
+
+impl InvoiceTotals {
+    /// The amounts of the invoice lines, in the order of the lines
+    #[inline(always)]
+    #[must_use]
+    pub fn lines(&self) -> &[LineTotal] {
+        &self.lines
+    }
+
+    /// The net subtotals and tax amounts per tax rate, in order of first
+    /// occurrence
+    #[inline(always)]
+    #[must_use]
+    pub fn subtotals(&self) -> &[TaxAmount] {
+        self.breakdown.taxes()
+    }
+
+    /// The split of the grand total into net amount and taxes
+    #[inline(always)]
+    #[must_use]
+    pub const fn tax_breakdown(&self) -> &TaxBreakdown {
+        &self.breakdown
+    }
+
+    /// The total net amount
+    #[inline(always)]
+    #[must_use]
+    pub const fn net(&self) -> Money {
+        self.breakdown.net()
+    }
+
+    /// The total tax amount
+    #[inline]
+    #[must_use]
+    pub fn tax(&self) -> Money {
+        self.breakdown.tax()
+    }
+
+    /// The grand total, i.e. total net amount plus total tax amount
+    #[inline(always)]
+    #[must_use]
+    pub const fn gross(&self) -> Money {
+        self.breakdown.gross()
+    }
+}
@@ -0,0 +1,549 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use fpdec::Decimal;
+
+use crate::{AmountT, Currency, Date, Money, MoneyError, Quantity};
+
+/// Account of a `Ledger`, identified by its code.
+///
+/// An account is either restricted to a single currency or accepts postings
+/// in any currency (multi-currency account).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Account {
+    code: String,
+    name: String,
+    currency: Option<Currency>,
+}
+
+impl Account {
+    /// Returns a new account accepting only postings in `currency`.
+    #[must_use]
+    pub fn new(code: &str, name: &str, currency: Currency) -> Self {
+        Self {
+            code: code.into(),
+            name: name.into(),
+            currency: Some(currency),
+        }
+    }
+
+    /// Returns a new account accepting postings in any currency.
+    #[must_use]
+    pub fn multi_currency(code: &str, name: &str) -> Self {
+        Self {
+            code: code.into(),
+            name: name.into(),
+            currency: None,
+        }
+    }
+
+    /// The code of `self`
+    #[inline(always)]
+    #[must_use]
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The name of `self`
+    #[inline(always)]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The currency of `self`, or `None` if `self` is a multi-currency
+    /// account
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Option<Currency> {
+        self.currency
+    }
+}
+
+/// Side of an account a posting is booked to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Side {
+    /// Left side of an account
+    Debit,
+    /// Right side of an account
+    Credit,
+}
+
+/// Single debit or credit of an amount to an account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Posting {
+    account: String,
+    side: Side,
+    amount: Money,
+}
+
+impl Posting {
+    /// The code of the account
+    #[inline(always)]
+    #[must_use]
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// The side of the account
+    #[inline(always)]
+    #[must_use]
+    pub const fn side(&self) -> Side {
+        self.side
+    }
+
+    /// The amount booked
+    #[inline(always)]
+    #[must_use]
+    pub const fn amount(&self) -> Money {
+        self.amount
+    }
+
+    // Returns the amount, negated in case of a credit.
+    fn signed_amount(&self) -> AmountT {
+        match self.side {
+            Side::Debit => self.amount.amount,
+            Side::Credit => -self.amount.amount,
+        }
+    }
+}
+
+/// Business transaction recorded as a set of postings.
+///
+/// An entry is balanced if, for each currency, the sum of its debits equals
+/// the sum of its credits.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Date, Dec, Decimal, JournalEntry, EUR};
+/// let entry = JournalEntry::new(Date::new(2024, 3, 1), "Office supplies")
+///     .debit("6800", Dec!(100) * EUR)
+///     .debit("1576", Dec!(19) * EUR)
+///     .credit("1200", Dec!(119) * EUR);
+/// assert!(entry.check_balance().is_ok());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JournalEntry {
+    date: Date,
+    description: String,
+    postings: Vec<Posting>,
+}
+
+impl JournalEntry {
+    /// Returns a new journal entry without postings.
+    #[must_use]
+    pub fn new(date: Date, description: &str) -> Self {
+        Self {
+            date,
+            description: description.into(),
+            postings: Vec::new(),
+        }
+    }
+
+    /// Returns `self` with a debit of `amount` to `account` added.
+    #[must_use]
+    pub fn debit(self, account: &str, amount: Money) -> Self {
+        self.add_posting(account, Side::Debit, amount)
+    }
+
+    /// Returns `self` with a credit of `amount` to `account` added.
+    #[must_use]
+    pub fn credit(self, account: &str, amount: Money) -> Self {
+        self.add_posting(account, Side::Credit, amount)
+    }
+
+    fn add_posting(
+        mut self,
+        account: &str,
+        side: Side,
+        amount: Money,
+    ) -> Self {
+        self.postings.push(Posting {
+            account: account.into(),
+            side,
+            amount,
+        });
+        self
+    }
+
+    /// The booking date of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn date(&self) -> Date {
+        self.date
+    }
+
+    /// The description of `self`
+    #[inline(always)]
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The postings of `self`
+    #[inline(always)]
+    #[must_use]
+    pub fn postings(&self) -> &[Posting] {
+        &self.postings
+    }
+
+    /// Checks whether `self` is balanced.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * `self` has no postings
+    /// * the amount of a posting is negative
+    /// * the debits and credits of `self` are not equal for some currency
+    pub fn check_balance(&self) -> Result<(), MoneyError> {
+        if self.postings.is_empty() {
+            return Err(MoneyError::EmptyEntry);
+        }
+        let mut sums: BTreeMap<Currency, (AmountT, AmountT)> =
+            BTreeMap::new();
+        for posting in &self.postings {
+            if posting.amount.is_negative() {
+                return Err(MoneyError::NegativeAmount {
+                    account: posting.account.clone(),
+                    amount: posting.amount,
+                });
+            }
+            let sum = sums
+                .entry(posting.amount.unit)
+                .or_insert((Decimal::ZERO, Decimal::ZERO));
+            match posting.side {
+                Side::Debit => sum.0 += posting.amount.amount,
+                Side::Credit => sum.1 += posting.amount.amount,
+            }
+        }
+        for (currency, (debit, credit)) in sums {
+            if debit != credit {
+                return Err(MoneyError::UnbalancedEntry {
+                    debit: Money::new(debit, currency),
+                    credit: Money::new(credit, currency),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Single line of a `TrialBalance`: the balance of an account in one
+/// currency, shown as debit or credit balance.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrialBalanceLine {
+    account: String,
+    debit: Money,
+    credit: Money,
+}
+
+impl TrialBalanceLine {
+    /// The code of the account
+    #[inline(always)]
+    #[must_use]
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// The debit balance of the account (zero in case of a credit balance)
+    #[inline(always)]
+    #[must_use]
+    pub const fn debit(&self) -> Money {
+        self.debit
+    }
+
+    /// The credit balance of the account (zero in case of a debit balance)
+    #[inline(always)]
+    #[must_use]
+    pub const fn credit(&self) -> Money {
+        self.credit
+    }
+
+    /// The currency of the balance
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.debit.unit
+    }
+}
+
+/// List of the balances of all accounts of a `Ledger` at a given date.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrialBalance {
+    as_of: Date,
+    lines: Vec<TrialBalanceLine>,
+}
+
+impl TrialBalance {
+    /// The date of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn as_of(&self) -> Date {
+        self.as_of
+    }
+
+    /// The lines of `self`, ordered by account code and currency
+    #[inline(always)]
+    #[must_use]
+    pub fn lines(&self) -> &[TrialBalanceLine] {
+        &self.lines
+    }
+
+    /// The currencies of the balances contained in `self`, in ascending order
+    #[must_use]
+    pub fn currencies(&self) -> Vec<Currency> {
+        let mut currencies: Vec<Currency> =
+            self.lines.iter().map(TrialBalanceLine::currency).collect();
+        currencies.sort_unstable();
+        currencies.dedup();
+        currencies
+    }
+
+    /// The sum of the debit balances in `currency`
+    #[must_use]
+    pub fn total_debit(&self, currency: Currency) -> Money {
+        self.lines
+            .iter()
+            .filter(|line| line.currency() == currency)
+            .fold(Money::zero(currency), |acc, line| acc + line.debit)
+    }
+
+    /// The sum of the credit balances in `currency`
+    #[must_use]
+    pub fn total_credit(&self, currency: Currency) -> Money {
+        self.lines
+            .iter()
+            .filter(|line| line.currency() == currency)
+            .fold(Money::zero(currency), |acc, line| acc + line.credit)
+    }
+
+    /// Returns `true` if the total debits equal the total credits for each
+    /// currency, otherwise `false`.
+    #[must_use]
+    pub fn is_balanced(&self) -> bool {
+        self.currencies().into_iter().all(|currency| {
+            self.total_debit(currency) == self.total_credit(currency)
+        })
+    }
+}
+
+/// Double-entry bookkeeping: a set of accounts and the journal entries
+/// posted to them.
+///
+/// Only balanced entries (see `JournalEntry::check_balance`) referring to
+/// existing accounts in matching currencies can be posted, so that the
+/// ledger as a whole is always balanced.
+///
+/// The balance of an account is the sum of its debits minus the sum of its
+/// credits, i.e. positive for a debit balance and negative for a credit
+/// balance.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{
+/// #     Account, Date, Dec, Decimal, JournalEntry, Ledger, EUR,
+/// # };
+/// let mut ledger = Ledger::new();
+/// ledger.open_account(Account::new("1200", "Bank", EUR))?;
+/// ledger.open_account(Account::new("8400", "Revenue", EUR))?;
+/// ledger.post(
+///     JournalEntry::new(Date::new(2024, 3, 1), "Invoice 4711")
+///         .debit("1200", Dec!(250) * EUR)
+///         .credit("8400", Dec!(250) * EUR),
+/// )?;
+/// let as_of = Date::new(2024, 3, 31);
+/// assert_eq!(ledger.balance("1200", EUR, as_of)?, Dec!(250) * EUR);
+/// assert_eq!(ledger.balance("8400", EUR, as_of)?, Dec!(-250) * EUR);
+/// assert!(ledger.trial_balance(as_of).is_balanced());
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Ledger {
+    accounts: BTreeMap<String, Account>,
+    entries: Vec<JournalEntry>,
+}
+
+impl Ledger {
+    /// Returns a new ledger without accounts.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            accounts: BTreeMap::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds `account` to `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `self` already contains an account with the same
+    /// code.
+    pub fn open_account(
+        &mut self,
+        account: Account,
+    ) -> Result<(), MoneyError> {
+        if self.accounts.contains_key(&account.code) {
+            return Err(MoneyError::DuplicateAccount { code: account.code });
+        }
+        self.accounts.insert(account.code.clone(), account);
+        Ok(())
+    }
+
+    /// Returns the account with the given code, or `None` if there is no
+    /// such account.
+    #[must_use]
+    pub fn account(&self, code: &str) -> Option<&Account> {
+        self.accounts.get(code)
+    }
+
+    /// Returns an iterator over the accounts of `self`, ordered by code.
+    pub fn accounts(&self) -> impl Iterator<Item = &Account> {
+        self.accounts.values()
+    }
+
+    /// The journal entries posted to `self`, in order of posting
+    #[inline(always)]
+    #[must_use]
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Posts `entry` to `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * `entry` is not balanced (see `JournalEntry::check_balance`)
+    /// * a posting refers to an unknown account
+    /// * the currency of a posting differs from the currency of its account
+    pub fn post(&mut self, entry: JournalEntry) -> Result<(), MoneyError> {
+        entry.check_balance()?;
+        for posting in &entry.postings {
+            let account =
+                self.accounts.get(&posting.account).ok_or_else(|| {
+                    MoneyError::UnknownAccount {
+                        code: posting.account.clone(),
+                    }
+                })?;
+            if let Some(currency) = account.currency {
+                if posting.amount.unit != currency {
+                    return Err(MoneyError::CurrencyMismatch {
+                        expected: currency,
+                        found: posting.amount.unit,
+                    });
+                }
+            }
+        }
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Returns the balance in `currency` of the account with the given code,
+    /// taking into account all entries dated on or before `as_of`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if there is no account with the given code.
+    pub fn balance(
+        &self,
+        code: &str,
+        currency: Currency,
+        as_of: Date,
+    ) -> Result<Money, MoneyError> {
+        self.check_account(code)?;
+        let amount = self
+            .postings_until(as_of)
+            .filter(|posting| {
+                posting.account == code && posting.amount.unit == currency
+            })
+            .fold(Decimal::ZERO, |acc, posting| {
+                acc + posting.signed_amount()
+            });
+        Ok(Money::new(amount, currency))
+    }
+
+    /// Returns the balances of the account with the given code, one for each
+    /// currency posted to the account on or before `as_of`, ordered by
+    /// currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if there is no account with the given code.
+    pub fn balances(
+        &self,
+        code: &str,
+        as_of: Date,
+    ) -> Result<Vec<Money>, MoneyError> {
+        self.check_account(code)?;
+        let mut sums: BTreeMap<Currency, AmountT> = BTreeMap::new();
+        for posting in self
+            .postings_until(as_of)
+            .filter(|posting| posting.account == code)
+        {
+            *sums.entry(posting.amount.unit).or_insert(Decimal::ZERO) +=
+                posting.signed_amount();
+        }
+        Ok(sums
+            .into_iter()
+            .map(|(currency, amount)| Money::new(amount, currency))
+            .collect())
+    }
+
+    /// Returns the trial balance of `self`, taking into account all entries
+    /// dated on or before `as_of`.
+    ///
+    /// The trial balance contains a line for each account and currency
+    /// having postings on or before `as_of`.
+    #[must_use]
+    pub fn trial_balance(&self, as_of: Date) -> TrialBalance {
+        let mut sums: BTreeMap<(&str, Currency), AmountT> = BTreeMap::new();
+        for posting in self.postings_until(as_of) {
+            *sums
+                .entry((posting.account.as_str(), posting.amount.unit))
+                .or_insert(Decimal::ZERO) += posting.signed_amount();
+        }
+        let lines = sums
+            .into_iter()
+            .map(|((account, currency), amount)| {
+                let balance = Money::new(amount, currency);
+                let zero = Money::zero(currency);
+                TrialBalanceLine {
+                    account: account.into(),
+                    debit: if balance.is_negative() { zero } else { balance },
+                    credit: if balance.is_negative() {
+                        -balance
+                    } else {
+                        zero
+                    },
+                }
+            })
+            .collect();
+        TrialBalance { as_of, lines }
+    }
+
+    fn check_account(&self, code: &str) -> Result<(), MoneyError> {
+        if self.accounts.contains_key(code) {
+            Ok(())
+        } else {
+            Err(MoneyError::UnknownAccount { code: code.into() })
+        }
+    }
+
+    fn postings_until(&self, as_of: Date) -> impl Iterator<Item = &Posting> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.date <= as_of)
+            .flat_map(|entry| entry.postings.iter())
+    }
+}
@@ -54,6 +54,8 @@
 #![warn(clippy::use_self)]
 #![warn(clippy::used_underscore_binding)]
 #![warn(clippy::wildcard_imports)]
+// getters returning `Decimal` are marked `#[must_use]` like all others
+#![allow(clippy::double_must_use)]
 
 extern crate alloc;
 
@@ -64,35 +66,91 @@ pub use core::cmp::Ordering;
 pub use core::fmt;
 #[doc(hidden)]
 pub use core::ops::{Add, Div, Mul, Sub};
+use core::{
+    cmp::min,
+    fmt::Write,
+    hash::{Hash, Hasher},
+    ops::{AddAssign, DivAssign, MulAssign, Neg, SubAssign},
+    str::FromStr,
+};
 
-pub use currency::Currency;
-use currency::CurrencyRegistry;
-use currency_info::CurrencyKeyError;
-pub use exchange::ExchangeRate;
-use fpdec::{DivRounded, MulRounded, Round};
+pub use amortization::{Amortization, AmortizationSchedule, Installment};
+pub use cash_flow::{irr, npv, xirr, xnpv};
+pub use conversion::{ConversionQuote, ConversionTerms, Fee};
+use currency::currency_info;
+pub use currency::{Currency, CurrencyEvent, CurrencyRegistry};
+pub use currency_info::{CurrencyKeyError, SymbolRules};
+pub use date::Date;
+pub use day_count::DayCount;
+pub use error::MoneyError;
+pub use exchange::{ExchangeRate, TargetRounding};
+use fpdec::{DivRounded, MulRounded, Round, MAX_N_FRAC_DIGITS};
+pub use fpdec::{ParseDecimalError, RoundingMode};
+use fpdec_core::{i128_div_mod_floor, ten_pow};
+pub use interest::{Compounding, InterestRate, InterestRounding};
+pub use invoice::{Invoice, InvoiceLine, InvoiceTotals, LineTotal};
 pub use iso_4217::ISOCurrency;
+pub use ledger::{
+    Account, JournalEntry, Ledger, Posting, Side, TrialBalance,
+    TrialBalanceLine,
+};
+pub use money_per_qty::{MoneyPerQty, MoneyPerQtyDisplay};
+pub use ordering::OrderedMoney;
+pub use percent::{BasisPoints, Percent};
+pub use position::{CostMethod, CurrencyPosition};
+pub use price::Price;
+pub use pricing::{
+    Adjustment, LineItem, PricedLine, PricingBreakdown, PricingRule,
+    PricingRules, RuleScope,
+};
 pub use quantities::{
     Amnt, AmountT, Dec, Decimal, Quantity, Rate, SIPrefix, Unit,
 };
+pub use range::MoneyRange;
+pub use tax::{TaxAmount, TaxBreakdown, TaxRounding};
+pub use unrounded::UnroundedMoney;
 
+mod aggregate;
+mod amortization;
+mod cash_flow;
+mod conversion;
 mod currency;
 mod currency_info;
+mod date;
+mod day_count;
+mod error;
 mod exchange;
+mod interest;
+mod invoice;
 mod iso_4217;
+mod ledger;
+mod math;
+mod money_per_qty;
+mod ordering;
+mod percent;
+mod position;
+mod price;
+mod pricing;
+mod range;
+mod tax;
+mod unrounded;
 
-static CURRENCY_REGISTRY: CurrencyRegistry = CurrencyRegistry::new();
+static CURRENCY_REGISTRY: CurrencyRegistry = CurrencyRegistry::new_global();
 
 #[allow(clippy::multiple_inherent_impl)]
 impl Currency {
-    /// Returns a newly registered `Currency` with the given attributes.
+    /// Returns a `Currency` with the given attributes, newly registered in
+    /// the global registry.
     ///
     /// # Errors
     ///
     /// Returns `Err` if
-    /// * the given symbol is empty or contains only white-space or non-ascii
-    ///   chars,
-    /// * a `Currency` with a key derived from the given symbol is already
-    ///   registered.
+    /// * the given symbol does not comply with the default `SymbolRules`,
+    /// * a `Currency` with the given symbol is already registered,
+    /// * a `Currency` with the given symbol is already known with different
+    ///   attributes,
+    /// * the key derived from the given symbol collides with the key of a
+    ///   currency with a different symbol.
     #[inline]
     pub fn new(
         symbol: &str,
@@ -102,10 +160,77 @@ impl Currency {
         CURRENCY_REGISTRY.register_currency(symbol, name, minor_units)
     }
 
+    /// Changes name and minor unit of `self`, which must be a custom
+    /// currency registered in the global registry.
+    ///
+    /// See `CurrencyRegistry::update_currency` for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if
+    /// * `self` is an ISO 4217 currency,
+    /// * `self` is not registered in the global registry.
+    #[inline]
+    pub fn update(
+        &self,
+        name: &str,
+        minor_units: u8,
+    ) -> Result<(), CurrencyKeyError> {
+        CURRENCY_REGISTRY.update_currency(self, name, minor_units)
+    }
+
+    /// Removes `self`, which must be a custom currency, from the global
+    /// registry.
+    ///
+    /// See `CurrencyRegistry::deregister_currency` for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if
+    /// * `self` is an ISO 4217 currency,
+    /// * `self` is not registered in the global registry.
+    #[inline]
+    pub fn deregister(&self) -> Result<(), CurrencyKeyError> {
+        CURRENCY_REGISTRY.deregister_currency(self)
+    }
+
     /// Returns the minor unit of `self`.
     #[inline]
+    #[must_use]
     pub fn minor_unit(&self) -> u8 {
-        CURRENCY_REGISTRY.get_currency_info(self).minor_unit
+        currency_info(self).minor_unit
+    }
+
+    /// Returns the symbol (code) used to represent `self`.
+    ///
+    /// Other than `Unit::symbol`, this does not allocate a new `String`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::HKD;
+    /// assert_eq!(HKD.code(), "HKD");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        currency_info(self).symbol
+    }
+
+    /// Returns the name of `self`.
+    ///
+    /// Other than `Unit::name`, this does not allocate a new `String`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::HKD;
+    /// assert_eq!(HKD.name_str(), "Hong Kong Dollar");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn name_str(&self) -> &'static str {
+        currency_info(self).name
     }
 }
 
@@ -114,23 +239,23 @@ impl Unit for Currency {
 
     #[inline]
     fn iter() -> impl Iterator<Item = Self> {
-        CURRENCY_REGISTRY.get_registered_currencies().into_iter()
+        CURRENCY_REGISTRY.currencies().into_iter()
     }
 
     /// Returns `Some(curr)` where `curr.symbol()` == `symbol`, or `None` if
     /// there is no such currency.
     fn from_symbol(symbol: &str) -> Option<Self> {
-        CURRENCY_REGISTRY.get_currency_from_symbol(symbol)
+        CURRENCY_REGISTRY.currency_from_symbol(symbol)
     }
 
     #[inline]
     fn name(&self) -> String {
-        CURRENCY_REGISTRY.get_currency_info(self).name
+        self.name_str().into()
     }
 
     #[inline]
     fn symbol(&self) -> String {
-        CURRENCY_REGISTRY.get_currency_info(self).symbol
+        self.code().into()
     }
 
     #[inline(always)]
@@ -140,8 +265,9 @@ impl Unit for Currency {
 }
 
 impl fmt::Display for Currency {
+    #[inline]
     fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
-        <Self as Unit>::fmt(self, form)
+        fmt::Display::fmt(self.code(), form)
     }
 }
 
@@ -590,6 +716,137 @@ impl Quantity for Money {
     }
 }
 
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns a money amount of zero in the given `Currency` unit.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Money, EUR};
+    /// let m = Money::zero(EUR);
+    /// assert!(m.is_zero());
+    /// assert_eq!(m.to_string(), "0.00 EUR");
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn zero(currency: Currency) -> Self {
+        Self {
+            amount: AmountT::ZERO,
+            unit: currency,
+        }
+    }
+
+    /// Returns `true` if the amount of `self` is equal to zero.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.amount.eq_zero()
+    }
+
+    /// Returns `true` if the amount of `self` is less than zero.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.amount.is_negative()
+    }
+
+    /// Returns `true` if the amount of `self` is greater than zero.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_positive(&self) -> bool {
+        self.amount.is_positive()
+    }
+
+    /// Returns the absolute value of `self`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, EUR};
+    /// let m = Dec!(-38.5) * EUR;
+    /// assert_eq!(m.abs(), Dec!(38.5) * EUR);
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn abs(&self) -> Self {
+        Self {
+            amount: self.amount.abs(),
+            unit: self.unit,
+        }
+    }
+
+    /// Returns the sign of the amount of `self`:
+    ///
+    /// * `0` if the amount is zero
+    /// * `1` if the amount is positive
+    /// * `-1` if the amount is negative
+    #[inline(always)]
+    #[must_use]
+    pub fn signum(&self) -> AmountT {
+        self.amount.signum()
+    }
+
+    /// Returns the sum of `self` and `other`, if both have the same
+    /// `Currency` unit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::CurrencyMismatch)` if the operands have
+    /// different `Currency` units.
+    #[inline]
+    pub fn try_add(self, other: Self) -> Result<Self, MoneyError> {
+        self.check_same_currency(&other)?;
+        Ok(Self::new(self.amount + other.amount, self.unit))
+    }
+
+    /// Returns the difference of `self` and `other`, if both have the same
+    /// `Currency` unit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::CurrencyMismatch)` if the operands have
+    /// different `Currency` units.
+    #[inline]
+    pub fn try_sub(self, other: Self) -> Result<Self, MoneyError> {
+        self.check_same_currency(&other)?;
+        Ok(Self::new(self.amount - other.amount, self.unit))
+    }
+
+    #[inline]
+    fn check_same_currency(&self, other: &Self) -> Result<(), MoneyError> {
+        if self.unit == other.unit {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch {
+                expected: self.unit,
+                found: other.unit,
+            })
+        }
+    }
+}
+
+impl FromStr for Money {
+    type Err = MoneyError;
+
+    /// Returns the `Money` value represented by `s`, looking up the currency
+    /// in the global registry.
+    ///
+    /// See `CurrencyRegistry::parse_money` for details.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Money, EUR};
+    /// let m: Money = "-38.50 EUR".parse().unwrap();
+    /// assert_eq!(m, Dec!(-38.5) * EUR);
+    /// ```
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CURRENCY_REGISTRY.parse_money(s)
+    }
+}
+
 impl Eq for Money {}
 
 impl PartialEq<Self> for Money {
@@ -601,6 +858,16 @@ impl PartialEq<Self> for Money {
     }
 }
 
+impl Hash for Money {
+    /// Feeds the `Currency` unit and the numerical amount of `self` into the
+    /// given `Hasher`, so that equal instances give equal hashes.
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.unit.hash(state);
+        self.amount.hash(state);
+    }
+}
+
 impl PartialOrd for Money {
     /// Returns the numerical order of the amounts of the compared instances,
     /// if their `Currency` units are identical, otherwise `None`.
@@ -697,23 +964,124 @@ impl fmt::Display for Money {
     /// assert_eq!(format!("{:>+12.2}", m), "  +38.50 EUR");
     /// ```
     fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let tmp: String;
-        let amnt_non_neg = self.amount().is_positive();
-        let abs_amnt = self.amount().abs();
-        if let Some(prec) = form.precision() {
-            tmp = format!("{:.*} {}", prec, abs_amnt, self.unit());
+        let info = currency_info(&self.unit);
+        #[allow(clippy::cast_possible_truncation)]
+        let prec = form.precision().map_or(info.minor_unit, |p| {
+            min(p, MAX_N_FRAC_DIGITS as usize) as u8
+        });
+        let body = MoneyRepr::new(self.amount.abs(), prec, info.symbol);
+        let sign = if self.amount.is_negative() {
+            Some('-')
+        } else if form.sign_plus() {
+            Some('+')
+        } else {
+            None
+        };
+        let mut counter = CharCounter(usize::from(sign.is_some()));
+        body.write_to(&mut counter)?;
+        let padding = form.width().map_or(0, |w| w.saturating_sub(counter.0));
+        if form.sign_aware_zero_pad() {
+            if let Some(sign) = sign {
+                form.write_char(sign)?;
+            }
+            write_n_chars(form, '0', padding)?;
+            return body.write_to(form);
+        }
+        #[allow(clippy::integer_division)]
+        let (pre, post) = match form.align() {
+            Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Center) => {
+                (padding / 2, padding - padding / 2)
+            }
+            _ => (padding, 0),
+        };
+        let fill = form.fill();
+        write_n_chars(form, fill, pre)?;
+        if let Some(sign) = sign {
+            form.write_char(sign)?;
+        }
+        body.write_to(form)?;
+        write_n_chars(form, fill, post)
+    }
+}
+
+/// Unsigned representation of a money amount, i.e. integral part, fractional
+/// part and currency symbol, which can be written without allocating a
+/// temporary string.
+struct MoneyRepr {
+    int: i128,
+    frac: i128,
+    prec: usize,
+    symbol: &'static str,
+}
+
+impl MoneyRepr {
+    #[allow(clippy::cast_possible_wrap)]
+    fn new(abs_amnt: AmountT, prec: u8, symbol: &'static str) -> Self {
+        let amnt = abs_amnt.round(prec as i8);
+        let n_frac_digits = amnt.n_frac_digits();
+        let (int, frac) =
+            i128_div_mod_floor(amnt.coefficient(), ten_pow(n_frac_digits));
+        Self {
+            int,
+            frac: frac * ten_pow(prec - n_frac_digits),
+            prec: prec as usize,
+            symbol,
+        }
+    }
+
+    fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        if self.prec > 0 {
+            write!(
+                w,
+                "{}.{:0width$} {}",
+                self.int,
+                self.frac,
+                self.symbol,
+                width = self.prec
+            )
         } else {
-            tmp = format!(
-                "{:.*} {}",
-                self.unit().minor_unit() as usize,
-                abs_amnt,
-                self.unit()
-            );
+            write!(w, "{} {}", self.int, self.symbol)
         }
-        form.pad_integral(amnt_non_neg, "", &tmp)
     }
 }
 
+/// Sink counting the chars written to it.
+struct CharCounter(usize);
+
+impl fmt::Write for CharCounter {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+fn write_n_chars<W: fmt::Write>(w: &mut W, c: char, n: usize) -> fmt::Result {
+    for _ in 0..n {
+        w.write_char(c)?;
+    }
+    Ok(())
+}
+
+// Writes the absolute value of `value`, formatted with `prec` fractional
+// digits (or as many as needed, if `prec` is `None`) and followed by
+// `suffix`. Sign, width, fill and alignment given in `form` are applied to
+// the complete string.
+fn write_padded(
+    form: &mut fmt::Formatter<'_>,
+    value: AmountT,
+    prec: Option<usize>,
+    suffix: &str,
+) -> fmt::Result {
+    let abs = value.abs();
+    let body = prec.map_or_else(
+        || format!("{abs}{suffix}"),
+        |prec| format!("{abs:.prec$}{suffix}"),
+    );
+    form.pad_integral(!value.is_negative(), "", &body)
+}
+
 impl Mul<Money> for AmountT {
     type Output = Money;
 
@@ -770,6 +1138,107 @@ impl Div<AmountT> for Money {
     }
 }
 
+impl Neg for Money {
+    type Output = Self;
+
+    /// Returns a new instance of `Money`, with the same `Currency` unit as
+    /// `self` and with the negated amount of `self`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, EUR};
+    /// let m = Dec!(38.5) * EUR;
+    /// assert_eq!(-m, Dec!(-38.5) * EUR);
+    /// ```
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Self {
+            amount: -self.amount,
+            unit: self.unit,
+        }
+    }
+}
+
+impl Neg for &Money {
+    type Output = Money;
+
+    /// Returns a new instance of `Money`, with the same `Currency` unit as
+    /// `self` and with the negated amount of `self`.
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        -*self
+    }
+}
+
+// Implements the binary operator given by `$trait` / `$method` for all
+// combinations of owned and borrowed operands, based on the impl for owned
+// operands.
+macro_rules! forward_ref_binop {
+    (impl $trait:ident, $method:ident for $lhs:ty, $rhs:ty) => {
+        impl $trait<&$rhs> for $lhs {
+            type Output = <$lhs as $trait<$rhs>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $trait::$method(self, *rhs)
+            }
+        }
+
+        impl $trait<$rhs> for &$lhs {
+            type Output = <$lhs as $trait<$rhs>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                $trait::$method(*self, rhs)
+            }
+        }
+
+        impl $trait<&$rhs> for &$lhs {
+            type Output = <$lhs as $trait<$rhs>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $trait::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+forward_ref_binop!(impl Add, add for Money, Money);
+forward_ref_binop!(impl Sub, sub for Money, Money);
+forward_ref_binop!(impl Div, div for Money, Money);
+forward_ref_binop!(impl Mul, mul for Money, AmountT);
+forward_ref_binop!(impl Div, div for Money, AmountT);
+forward_ref_binop!(impl Mul, mul for AmountT, Money);
+
+// Implements the compound assignment operator given by `$trait` / `$method`
+// for owned and borrowed right operands, based on the binary operator
+// `$op` / `$op_method`.
+macro_rules! impl_op_assign {
+    (impl $trait:ident, $method:ident ($op:ident, $op_method:ident)
+     for $lhs:ty, $rhs:ty) => {
+        impl $trait<$rhs> for $lhs {
+            #[inline(always)]
+            fn $method(&mut self, rhs: $rhs) {
+                *self = $op::$op_method(*self, rhs);
+            }
+        }
+
+        impl $trait<&$rhs> for $lhs {
+            #[inline(always)]
+            fn $method(&mut self, rhs: &$rhs) {
+                *self = $op::$op_method(*self, *rhs);
+            }
+        }
+    };
+}
+
+impl_op_assign!(impl AddAssign, add_assign (Add, add) for Money, Money);
+impl_op_assign!(impl SubAssign, sub_assign (Sub, sub) for Money, Money);
+impl_op_assign!(impl MulAssign, mul_assign (Mul, mul) for Money, AmountT);
+impl_op_assign!(impl DivAssign, div_assign (Div, div) for Money, AmountT);
+
 impl<TQ: Quantity> Mul<Rate<TQ, Self>> for Money {
     type Output = TQ;
 
@@ -0,0 +1,278 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+//! Elementary functions on `Decimal`, calculated with the maximum number of
+//! fractional digits supported by `Decimal`.
+
+use fpdec::{Decimal, RoundingMode, MAX_N_FRAC_DIGITS};
+use fpdec_core::{i128_div_rounded, i128_mul_div_ten_pow_rounded, ten_pow};
+
+use crate::AmountT;
+
+// ln(2), rounded to 18 fractional digits
+const LN_2: Decimal = Decimal::new_raw(693_147_180_559_945_309, 18);
+// Max argument of `exp` (e^42 < 1.8e18)
+const MAX_EXP_ARG: Decimal = Decimal::new_raw(42, 0);
+const HALF: Decimal = Decimal::new_raw(5, 1);
+
+/// Returns `x` rounded to `n_frac_digits` fractional digits according to
+/// `mode`.
+pub(crate) fn round_with_mode(
+    x: AmountT,
+    n_frac_digits: u8,
+    mode: RoundingMode,
+) -> AmountT {
+    if n_frac_digits >= x.n_frac_digits() {
+        return x;
+    }
+    let divisor = ten_pow(x.n_frac_digits() - n_frac_digits);
+    Decimal::new_raw(
+        i128_div_rounded(x.coefficient(), divisor, Some(mode)),
+        n_frac_digits,
+    )
+}
+
+/// Returns `x * y`, rounded like `Decimal`'s `Mul`, or `None` if the result
+/// exceeds the range of `Decimal`.
+pub(crate) fn checked_mul(x: AmountT, y: AmountT) -> Option<AmountT> {
+    let n_frac_digits = x.n_frac_digits() + y.n_frac_digits();
+    if n_frac_digits <= MAX_N_FRAC_DIGITS {
+        return Some(Decimal::new_raw(
+            x.coefficient().checked_mul(y.coefficient())?,
+            n_frac_digits,
+        ));
+    }
+    Some(Decimal::new_raw(
+        i128_mul_div_ten_pow_rounded(
+            x.coefficient(),
+            y.coefficient(),
+            n_frac_digits - MAX_N_FRAC_DIGITS,
+            None,
+        )?,
+        MAX_N_FRAC_DIGITS,
+    ))
+}
+
+/// Returns `base` raised to the power of `exp`.
+///
+/// ### Panics
+/// The function panics in the following cases:
+/// * the result exceeds the range of `Decimal`
+pub(crate) fn powi(base: AmountT, exp: u64) -> AmountT {
+    checked_powi(base, exp).expect("Result exceeds the range of Decimal.")
+}
+
+/// Returns `base` raised to the power of `exp`, or `None` if the result
+/// exceeds the range of `Decimal`.
+pub(crate) fn checked_powi(
+    mut base: AmountT,
+    mut exp: u64,
+) -> Option<AmountT> {
+    let mut res = Decimal::ONE;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = checked_mul(res, base)?;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = checked_mul(base, base)?;
+        }
+    }
+    Some(res)
+}
+
+/// Returns `base` raised to the power of `exp`, or `None` if the result
+/// exceeds the range of `Decimal`.
+///
+/// ### Panics
+/// The function panics in the following cases:
+/// * `base` <= 0
+pub(crate) fn pow(base: AmountT, exp: AmountT) -> Option<AmountT> {
+    if exp.eq_zero() || base.eq_one() {
+        return Some(Decimal::ONE);
+    }
+    if exp.fract().eq_zero() && !exp.is_negative() {
+        if let Ok(n) = u64::try_from(exp) {
+            return checked_powi(base, n);
+        }
+    }
+    self::exp(checked_mul(exp, ln(base))?)
+}
+
+/// Returns the natural logarithm of `x`.
+///
+/// ### Panics
+/// The function panics in the following cases:
+/// * `x` <= 0
+pub(crate) fn ln(x: AmountT) -> AmountT {
+    assert!(x.is_positive(), "Logarithm of a number <= 0 is undefined.");
+    if x > Decimal::ONE {
+        return -ln(Decimal::ONE / x);
+    }
+    // reduce x to y = x * 2^k with y in [0.5, 1]
+    let mut y = x;
+    let mut k = 0_u32;
+    while y < HALF {
+        y *= Decimal::TWO;
+        k += 1;
+    }
+    // ln(y) = 2 * atanh(s) = 2 * (s + s^3/3 + s^5/5 + ...),
+    // with s = (y - 1) / (y + 1) in [-1/3, 0]
+    let s = (y - Decimal::ONE) / (y + Decimal::ONE);
+    let s2 = s * s;
+    let mut term = s;
+    let mut sum = s;
+    let mut n = 1_u32;
+    while !term.eq_zero() {
+        term *= s2;
+        n += 2;
+        sum += term / n;
+    }
+    sum * Decimal::TWO - LN_2 * k
+}
+
+/// Returns e raised to the power of `x`, or `None` if `x` > 42, i.e. if the
+/// result may exceed the range of `Decimal`.
+///
+/// Results less than 1e-18 are returned as zero.
+pub(crate) fn exp(x: AmountT) -> Option<AmountT> {
+    if x.is_negative() {
+        if x < -MAX_EXP_ARG {
+            return Some(Decimal::ZERO);
+        }
+        return Some(Decimal::ONE / exp(-x)?);
+    }
+    if x > MAX_EXP_ARG {
+        return None;
+    }
+    // reduce x to y = x / 2^k with y < 0.5
+    let mut y = x;
+    let mut k = 0_u32;
+    while y >= HALF {
+        y /= Decimal::TWO;
+        k += 1;
+    }
+    // e^y = 1 + y + y^2/2! + y^3/3! + ...
+    let mut term = Decimal::ONE;
+    let mut sum = Decimal::ONE;
+    let mut n = 0_u32;
+    while !term.eq_zero() {
+        n += 1;
+        term = term * y / n;
+        sum += term;
+    }
+    for _ in 0..k {
+        sum *= sum;
+    }
+    Some(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use fpdec::Round;
+
+    use super::*;
+
+    #[test]
+    fn test_round_with_mode() {
+        let x = Decimal::new_raw(-12_345, 3);
+        assert_eq!(round_with_mode(x, 3, RoundingMode::RoundUp), x);
+        assert_eq!(
+            round_with_mode(x, 2, RoundingMode::RoundHalfEven),
+            Decimal::new_raw(-1234, 2)
+        );
+        assert_eq!(
+            round_with_mode(x, 2, RoundingMode::RoundHalfUp),
+            Decimal::new_raw(-1235, 2)
+        );
+        assert_eq!(
+            round_with_mode(x, 0, RoundingMode::RoundCeiling),
+            Decimal::new_raw(-12, 0)
+        );
+        assert_eq!(
+            round_with_mode(x, 0, RoundingMode::RoundFloor),
+            Decimal::new_raw(-13, 0)
+        );
+    }
+
+    #[test]
+    fn test_powi() {
+        assert_eq!(powi(Decimal::TWO, 0), Decimal::ONE);
+        assert_eq!(powi(Decimal::TWO, 10), Decimal::from(1024));
+        assert_eq!(
+            powi(Decimal::new_raw(105, 2), 3),
+            Decimal::new_raw(1_157_625, 6)
+        );
+        assert_eq!(checked_powi(Decimal::TEN, 40), None);
+        assert_eq!(
+            checked_powi(Decimal::new_raw(1, 6), 30),
+            Some(Decimal::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        let x = Decimal::new_raw(123_456_789, 9);
+        assert_eq!(checked_mul(x, x), Some(x * x));
+        let x = Decimal::new_raw(123_456_789_012_345_678_901, 18);
+        assert_eq!(checked_mul(x, x), Some(x * x));
+        assert_eq!(checked_mul(x, Decimal::from(-3)), Some(x * -3));
+        let x = Decimal::from(i64::MAX);
+        assert_eq!(checked_mul(x, x * 10_000), None);
+    }
+
+    #[test]
+    fn test_ln() {
+        assert_eq!(ln(Decimal::ONE), Decimal::ZERO);
+        assert_eq!(ln(Decimal::TWO).round(15), LN_2.round(15));
+        assert_eq!(ln(Decimal::new_raw(5, 1)).round(15), -LN_2.round(15));
+        // ln(10) = 2.302585092994045684...
+        assert_eq!(
+            ln(Decimal::TEN).round(15),
+            Decimal::new_raw(2_302_585_092_994_046, 15)
+        );
+    }
+
+    #[test]
+    fn test_exp() {
+        assert_eq!(exp(Decimal::ZERO), Some(Decimal::ONE));
+        // e = 2.718281828459045235...
+        assert_eq!(
+            exp(Decimal::ONE).unwrap().round(15),
+            Decimal::new_raw(2_718_281_828_459_045, 15)
+        );
+        assert_eq!(exp(ln(Decimal::TEN)).unwrap().round(12), Decimal::TEN);
+        assert_eq!(exp(Decimal::from(-50)), Some(Decimal::ZERO));
+        assert_eq!(exp(Decimal::from(43)), None);
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(
+            pow(Decimal::TWO, Decimal::from(10)),
+            Some(Decimal::from(1024))
+        );
+        assert_eq!(
+            pow(Decimal::from(4), Decimal::new_raw(5, 1))
+                .unwrap()
+                .round(15),
+            Decimal::TWO
+        );
+        assert_eq!(
+            pow(Decimal::from(4), Decimal::new_raw(-5, 1))
+                .unwrap()
+                .round(15),
+            Decimal::new_raw(5, 1)
+        );
+        assert_eq!(
+            pow(Decimal::from(1_000_000), Decimal::new_raw(305, 1)),
+            None
+        );
+    }
+}
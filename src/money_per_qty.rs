@@ -0,0 +1,185 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::format;
+use core::fmt;
+
+use quantities::{LinearScaledUnit, Rate, Unit};
+
+use crate::{
+    write_padded, AmountT, ExchangeRate, Money, MoneyError, Quantity,
+};
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns the rate of `self` per one `unit`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, EUR};
+    /// # use quantities::prelude::*;
+    /// # #[quantity]
+    /// # #[ref_unit(Kilogram, "kg", KILO, "Reference unit of quantity `Mass`")]
+    /// # #[unit(Gram, "g", NONE, 0.001, "0.001·kg")]
+    /// # struct Mass {}
+    /// let price = (Dec!(3.5) * EUR).per_unit(KILOGRAM);
+    /// assert_eq!(price * (Dec!(2) * KILOGRAM), Dec!(7) * EUR);
+    /// ```
+    #[inline(always)]
+    pub const fn per_unit<U: Unit>(
+        self,
+        unit: U,
+    ) -> Rate<Self, U::QuantityType> {
+        Rate::new(self.amount, self.unit, AmountT::ONE, unit)
+    }
+
+    /// Returns the rate of `self` per `qty`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, EUR};
+    /// # use quantities::prelude::*;
+    /// # #[quantity]
+    /// # #[ref_unit(Kilogram, "kg", KILO, "Reference unit of quantity `Mass`")]
+    /// # #[unit(Gram, "g", NONE, 0.001, "0.001·kg")]
+    /// # struct Mass {}
+    /// let price = (Dec!(1.25) * EUR).per(Dec!(100) * GRAM);
+    /// assert_eq!(price * (Dec!(1) * KILOGRAM), Dec!(12.50) * EUR);
+    /// ```
+    #[inline(always)]
+    pub fn per<PQ: Quantity>(self, qty: PQ) -> Rate<Self, PQ> {
+        Rate::from_qty_vals(self, qty)
+    }
+}
+
+/// Conversions and formatting of rates of money per some other quantity,
+/// like prices per kilogram or per liter.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Dec, Decimal, ExchangeRate, MoneyPerQty, EUR, USD};
+/// # use quantities::prelude::*;
+/// # #[quantity]
+/// # #[ref_unit(Kilogram, "kg", KILO, "Reference unit of quantity `Mass`")]
+/// # #[unit(Pound, "lb", 0.45359237, "0.45359237·kg")]
+/// # struct Mass {}
+/// let eur_per_kg = (Dec!(3.5) * EUR).per_unit(KILOGRAM);
+/// let usd_per_lb = eur_per_kg
+///     .convert_currency(ExchangeRate::new(EUR, 1, USD, Dec!(1.08)))?
+///     .convert_per_unit(POUND);
+/// assert_eq!(usd_per_lb.display().to_string(), "1.71 USD/lb");
+/// assert_eq!(format!("{:.4}", usd_per_lb.display()), "1.7146 USD/lb");
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+pub trait MoneyPerQty<PQ: Quantity>: Sized {
+    /// Returns the equivalent of `self` with its money side converted into
+    /// the other currency of `rate`.
+    ///
+    /// The term amount of `self` is multiplied by `rate` if its currency is
+    /// the unit currency of `rate`, and divided by `rate` if it is the term
+    /// currency. The result is not rounded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the currency of `self` is neither the unit nor the
+    /// term currency of `rate`.
+    fn convert_currency(
+        &self,
+        rate: ExchangeRate,
+    ) -> Result<Self, MoneyError>;
+
+    /// Returns the equivalent of `self` with its quantity side expressed in
+    /// `to_unit`, keeping the per unit multiple of `self`.
+    fn convert_per_unit(&self, to_unit: PQ::UnitType) -> Self
+    where
+        PQ::UnitType: LinearScaledUnit;
+
+    /// Returns an object implementing `Display` for `self`, rendering it
+    /// like "3.50 EUR/kg" or "12.50 EUR/100 g".
+    ///
+    /// The term amount is formatted with the precision given in the format
+    /// spec or, if none is given, with the minor unit of its currency.
+    fn display(&self) -> MoneyPerQtyDisplay<'_, PQ>;
+}
+
+impl<PQ: Quantity> MoneyPerQty<PQ> for Rate<Money, PQ> {
+    fn convert_currency(
+        &self,
+        rate: ExchangeRate,
+    ) -> Result<Self, MoneyError> {
+        let currency = self.term_unit();
+        let (term_amount, term_unit) = if currency == rate.unit_currency() {
+            (self.term_amount() * rate.rate(), rate.term_currency())
+        } else if currency == rate.term_currency() {
+            (self.term_amount() / rate.rate(), rate.unit_currency())
+        } else {
+            return Err(MoneyError::CurrencyMismatch {
+                expected: rate.unit_currency(),
+                found: currency,
+            });
+        };
+        Ok(Self::new(
+            term_amount,
+            term_unit,
+            self.per_unit_multiple(),
+            self.per_unit(),
+        ))
+    }
+
+    fn convert_per_unit(&self, to_unit: PQ::UnitType) -> Self
+    where
+        PQ::UnitType: LinearScaledUnit,
+    {
+        Self::new(
+            self.term_amount() * to_unit.ratio(&self.per_unit()),
+            self.term_unit(),
+            self.per_unit_multiple(),
+            to_unit,
+        )
+    }
+
+    #[inline(always)]
+    fn display(&self) -> MoneyPerQtyDisplay<'_, PQ> {
+        MoneyPerQtyDisplay(self)
+    }
+}
+
+/// Helper struct for formatting a `Rate<Money, PQ>`, returned by
+/// `MoneyPerQty::display`.
+pub struct MoneyPerQtyDisplay<'a, PQ: Quantity>(&'a Rate<Money, PQ>);
+
+impl<PQ: Quantity> fmt::Display for MoneyPerQtyDisplay<'_, PQ> {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rate = self.0;
+        let currency = rate.term_unit();
+        let prec = form
+            .precision()
+            .unwrap_or_else(|| currency.minor_unit() as usize);
+        let suffix = if rate.per_unit_multiple() == AmountT::ONE {
+            format!(" {}/{}", currency.code(), rate.per_unit().symbol())
+        } else {
+            format!(
+                " {}/{} {}",
+                currency.code(),
+                rate.per_unit_multiple(),
+                rate.per_unit().symbol()
+            )
+        };
+        write_padded(form, rate.term_amount(), Some(prec), &suffix)
+    }
+}
+
+impl<PQ: Quantity> fmt::Debug for MoneyPerQtyDisplay<'_, PQ> {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(form, "MoneyPerQtyDisplay(\"{self}\")")
+    }
+}
@@ -0,0 +1,112 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{cmp::Ordering, fmt};
+
+use crate::Money;
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns the total order of `self` and `other`: instances are ordered
+    /// by their `Currency` units first and then by their numerical amounts.
+    ///
+    /// In contrast to `PartialOrd for Money`, which only compares instances
+    /// having the same currency, this allows to sort collections of `Money`
+    /// in different currencies.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, EUR, USD};
+    /// let mut v = vec![Dec!(5) * USD, Dec!(7) * EUR, Dec!(-3) * USD];
+    /// v.sort_by(|a, b| a.total_cmp(b));
+    /// assert_eq!(v, [Dec!(7) * EUR, Dec!(-3) * USD, Dec!(5) * USD]);
+    /// ```
+    #[must_use]
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        self.unit
+            .cmp(&other.unit)
+            .then_with(|| self.amount.cmp(&other.amount))
+    }
+}
+
+/// Wrapper around `Money` implementing `Ord` according to
+/// `Money::total_cmp`, e.g. for use as key of a `BTreeMap`.
+///
+/// Example:
+///
+/// ```rust
+/// # use std::collections::BTreeSet;
+/// # use moneta::{Dec, Decimal, OrderedMoney, EUR, USD};
+/// let set: BTreeSet<OrderedMoney> = [Dec!(5) * USD, Dec!(7) * EUR]
+///     .into_iter()
+///     .map(OrderedMoney::from)
+///     .collect();
+/// let first = set.first().unwrap();
+/// assert_eq!(first.money(), Dec!(7) * EUR);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize, ::serde::Serialize)
+)]
+pub struct OrderedMoney(Money);
+
+impl OrderedMoney {
+    /// Returns a new instance of `OrderedMoney` wrapping `money`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(money: Money) -> Self {
+        Self(money)
+    }
+
+    /// The wrapped `Money` value
+    #[inline(always)]
+    #[must_use]
+    pub const fn money(&self) -> Money {
+        self.0
+    }
+}
+
+impl From<Money> for OrderedMoney {
+    #[inline(always)]
+    fn from(money: Money) -> Self {
+        Self(money)
+    }
+}
+
+impl From<OrderedMoney> for Money {
+    #[inline(always)]
+    fn from(ordered: OrderedMoney) -> Self {
+        ordered.0
+    }
+}
+
+impl PartialOrd for OrderedMoney {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedMoney {
+    /// Returns the order of the wrapped values according to
+    /// `Money::total_cmp`.
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl fmt::Display for OrderedMoney {
+    #[inline(always)]
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, form)
+    }
+}
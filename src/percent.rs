@@ -0,0 +1,277 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{fmt, ops::Mul};
+
+use fpdec::{Decimal, MulRounded};
+
+use crate::{
+    aggregate::check_currency, write_padded, AmountT, Money, MoneyError,
+    Quantity,
+};
+
+/// Represents a percentage, i.e. a fraction given in hundredths.
+///
+/// `Percent::new(Dec!(19))` denotes 19 %, i.e. the fraction 0.19.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Dec, Decimal, Percent, EUR};
+/// let vat = Percent::new(Dec!(19));
+/// assert_eq!(vat.to_string(), "19 %");
+/// assert_eq!(vat.as_fraction(), Dec!(0.19));
+/// let m = Dec!(24.99) * EUR;
+/// assert_eq!(m * vat, Dec!(4.75) * EUR);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize, ::serde::Serialize)
+)]
+pub struct Percent(AmountT);
+
+/// Represents an amount of basis points, i.e. a fraction given in
+/// ten-thousandths.
+///
+/// `BasisPoints::new(Dec!(25))` denotes 25 bp, i.e. 0.25 % or the fraction
+/// 0.0025.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{BasisPoints, Dec, Decimal, Percent, EUR};
+/// let spread = BasisPoints::new(Dec!(25));
+/// assert_eq!(spread.to_string(), "25 bp");
+/// assert_eq!(Percent::from(spread), Percent::new(Dec!(0.25)));
+/// let m = Dec!(10000) * EUR;
+/// assert_eq!(m * spread, Dec!(25) * EUR);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize, ::serde::Serialize)
+)]
+pub struct BasisPoints(AmountT);
+
+impl Percent {
+    /// 100 %
+    pub const HUNDRED: Self = Self(Decimal::new_raw(100, 0));
+
+    /// Returns a new instance of `Percent`, with `value` given in percent.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(value: AmountT) -> Self {
+        Self(value)
+    }
+
+    /// Returns a new instance of `Percent` equivalent to the given fraction,
+    /// i.e. `Percent::from_fraction(Dec!(0.19))` == `Percent::new(Dec!(19))`.
+    #[inline]
+    #[must_use]
+    pub fn from_fraction(fraction: AmountT) -> Self {
+        Self(fraction * Decimal::new_raw(100, 0))
+    }
+
+    /// The numerical value of `self`, given in percent.
+    #[inline(always)]
+    #[must_use]
+    pub const fn value(&self) -> AmountT {
+        self.0
+    }
+
+    /// The fraction represented by `self`, i.e. `self.value()` / 100.
+    #[inline]
+    #[must_use]
+    pub fn as_fraction(&self) -> AmountT {
+        self.0 * Decimal::new_raw(1, 2)
+    }
+}
+
+impl BasisPoints {
+    /// Returns a new instance of `BasisPoints`, with `value` given in basis
+    /// points.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(value: AmountT) -> Self {
+        Self(value)
+    }
+
+    /// Returns a new instance of `BasisPoints` equivalent to the given
+    /// fraction, i.e. `BasisPoints::from_fraction(Dec!(0.0025))` ==
+    /// `BasisPoints::new(Dec!(25))`.
+    #[inline]
+    #[must_use]
+    pub fn from_fraction(fraction: AmountT) -> Self {
+        Self(fraction * Decimal::new_raw(10000, 0))
+    }
+
+    /// The numerical value of `self`, given in basis points.
+    #[inline(always)]
+    #[must_use]
+    pub const fn value(&self) -> AmountT {
+        self.0
+    }
+
+    /// The fraction represented by `self`, i.e. `self.value()` / 10000.
+    #[inline]
+    #[must_use]
+    pub fn as_fraction(&self) -> AmountT {
+        self.0 * Decimal::new_raw(1, 4)
+    }
+}
+
+impl From<BasisPoints> for Percent {
+    #[inline]
+    fn from(bp: BasisPoints) -> Self {
+        Self(bp.0 * Decimal::new_raw(1, 2))
+    }
+}
+
+impl From<Percent> for BasisPoints {
+    #[inline]
+    fn from(pct: Percent) -> Self {
+        Self(pct.0 * Decimal::new_raw(100, 0))
+    }
+}
+
+impl fmt::Display for Percent {
+    /// Returns a formatted string representation of `self`.
+    ///
+    /// The numerical value of `self` is formatted with the precision given
+    /// in the format spec and appended by a blank and a percent sign; width,
+    /// fill and alignment are applied to the resulting string.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Percent};
+    /// let p = Percent::new(Dec!(7.5));
+    /// assert_eq!(format!("{p:>8.2}"), "  7.50 %");
+    /// ```
+    #[inline]
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_padded(form, self.0, form.precision(), " %")
+    }
+}
+
+impl fmt::Display for BasisPoints {
+    /// Returns a formatted string representation of `self`.
+    ///
+    /// The numerical value of `self` is formatted with the precision given
+    /// in the format spec and appended by a blank and "bp"; width, fill and
+    /// alignment are applied to the resulting string.
+    #[inline]
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_padded(form, self.0, form.precision(), " bp")
+    }
+}
+
+impl Mul<Percent> for Money {
+    type Output = Self;
+
+    /// Returns a new instance of `Money`, with the same `Currency` unit as
+    /// `self` and with an amount equal to `rhs` percent of `self.amount()`,
+    /// rounded to the number of fractional digits defined by `self.unit()`.
+    #[inline]
+    fn mul(self, rhs: Percent) -> Self::Output {
+        Self::Output::new(
+            self.amount()
+                .mul_rounded(rhs.as_fraction(), self.unit().minor_unit()),
+            self.unit(),
+        )
+    }
+}
+
+impl Mul<Money> for Percent {
+    type Output = Money;
+
+    /// Returns a new instance of `Money`, with the same `Currency` unit as
+    /// `rhs` and with an amount equal to `self` percent of `rhs.amount()`,
+    /// rounded to the number of fractional digits defined by `rhs.unit()`.
+    #[inline(always)]
+    fn mul(self, rhs: Money) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Mul<BasisPoints> for Money {
+    type Output = Self;
+
+    /// Returns a new instance of `Money`, with the same `Currency` unit as
+    /// `self` and with an amount equal to `rhs` basis points of
+    /// `self.amount()`, rounded to the number of fractional digits defined by
+    /// `self.unit()`.
+    #[inline]
+    fn mul(self, rhs: BasisPoints) -> Self::Output {
+        Self::Output::new(
+            self.amount()
+                .mul_rounded(rhs.as_fraction(), self.unit().minor_unit()),
+            self.unit(),
+        )
+    }
+}
+
+impl Mul<Money> for BasisPoints {
+    type Output = Money;
+
+    /// Returns a new instance of `Money`, with the same `Currency` unit as
+    /// `rhs` and with an amount equal to `self` basis points of
+    /// `rhs.amount()`, rounded to the number of fractional digits defined by
+    /// `rhs.unit()`.
+    #[inline(always)]
+    fn mul(self, rhs: Money) -> Self::Output {
+        rhs * self
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns the given percentage of `self`, rounded to the number of
+    /// fractional digits defined by `self.unit()`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Percent, EUR};
+    /// let m = Dec!(1234.56) * EUR;
+    /// assert_eq!(m.percent_of(Percent::new(Dec!(2.5))), Dec!(30.86) * EUR);
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn percent_of(&self, percent: Percent) -> Self {
+        *self * percent
+    }
+
+    /// Returns the ratio of `self` to `other`, expressed in percent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the operands have different `Currency` units or if
+    /// the amount of `other` is zero.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Percent, EUR};
+    /// let part = Dec!(30.86) * EUR;
+    /// let total = Dec!(1234.40) * EUR;
+    /// assert_eq!(part.ratio_to(&total)?, Percent::new(Dec!(2.5)));
+    /// # Ok::<(), moneta::MoneyError>(())
+    /// ```
+    pub fn ratio_to(&self, other: &Self) -> Result<Percent, MoneyError> {
+        check_currency(self.unit(), other)?;
+        if other.amount().eq_zero() {
+            return Err(MoneyError::DivisionByZero);
+        }
+        Ok(Percent(
+            (self.amount() * Decimal::new_raw(100, 0)) / other.amount(),
+        ))
+    }
+}
@@ -0,0 +1,308 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::collections::VecDeque;
+
+use fpdec::DivRounded;
+
+use crate::{
+    aggregate::check_currency, Currency, ExchangeRate, Money, MoneyError,
+    Quantity,
+};
+
+/// Method used to determine the cost of the part of a position being sold.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum CostMethod {
+    /// First in, first out: the oldest purchases are sold first.
+    #[default]
+    Fifo,
+    /// Last in, first out: the latest purchases are sold first.
+    Lifo,
+    /// All purchases are pooled at their average cost.
+    WeightedAverage,
+}
+
+// Part of a position bought in one trade (or, in case of the weighted
+// average cost method, the pool of all purchases).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Lot {
+    quantity: Money,
+    cost: Money,
+}
+
+/// Holding of a foreign currency, valued at cost in a base currency.
+///
+/// Purchases are recorded at the cost resulting from converting the amount
+/// bought by the given `ExchangeRate`, rounded to the minor unit of the base
+/// currency. When the position is reduced, the cost of the amount sold is
+/// determined according to the position's `CostMethod` and the difference
+/// between the proceeds and this cost is realized as profit (positive) or
+/// loss (negative).
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{
+/// #     CostMethod, CurrencyPosition, Dec, Decimal, ExchangeRate, EUR, USD,
+/// # };
+/// let mut pos = CurrencyPosition::new(USD, EUR, CostMethod::Fifo);
+/// pos.buy(Dec!(1000) * USD, ExchangeRate::new(USD, 1, EUR, Dec!(0.90)))?;
+/// pos.buy(Dec!(1000) * USD, ExchangeRate::new(USD, 1, EUR, Dec!(0.95)))?;
+/// let pnl =
+///     pos.sell(Dec!(1500) * USD, ExchangeRate::new(USD, 1, EUR, Dec!(0.96)))?;
+/// assert_eq!(pnl, Dec!(65) * EUR);
+/// assert_eq!(pos.quantity(), Dec!(500) * USD);
+/// assert_eq!(pos.cost_basis(), Dec!(475) * EUR);
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CurrencyPosition {
+    method: CostMethod,
+    lots: VecDeque<Lot>,
+    quantity: Money,
+    cost_basis: Money,
+    realized_pnl: Money,
+}
+
+impl CurrencyPosition {
+    /// Returns a new, empty position in `currency`, valued in
+    /// `base_currency` according to `method`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `currency` is equal to `base_currency`.
+    #[must_use]
+    pub fn new(
+        currency: Currency,
+        base_currency: Currency,
+        method: CostMethod,
+    ) -> Self {
+        match Self::try_new(currency, base_currency, method) {
+            Ok(pos) => pos,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Returns a new, empty position in `currency`, valued in
+    /// `base_currency` according to `method`, or an error if the currencies
+    /// are identical.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::IdenticalRateCurrencies)` if `currency` is
+    /// equal to `base_currency`.
+    pub fn try_new(
+        currency: Currency,
+        base_currency: Currency,
+        method: CostMethod,
+    ) -> Result<Self, MoneyError> {
+        if currency == base_currency {
+            return Err(MoneyError::IdenticalRateCurrencies(currency));
+        }
+        Ok(Self {
+            method,
+            lots: VecDeque::new(),
+            quantity: Money::zero(currency),
+            cost_basis: Money::zero(base_currency),
+            realized_pnl: Money::zero(base_currency),
+        })
+    }
+
+    /// The currency held
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.quantity.unit
+    }
+
+    /// The currency the position is valued in
+    #[inline(always)]
+    #[must_use]
+    pub const fn base_currency(&self) -> Currency {
+        self.cost_basis.unit
+    }
+
+    /// The method used to determine the cost of the amounts sold
+    #[inline(always)]
+    #[must_use]
+    pub const fn method(&self) -> CostMethod {
+        self.method
+    }
+
+    /// The amount held
+    #[inline(always)]
+    #[must_use]
+    pub const fn quantity(&self) -> Money {
+        self.quantity
+    }
+
+    /// The cost of the amount held, in base currency
+    #[inline(always)]
+    #[must_use]
+    pub const fn cost_basis(&self) -> Money {
+        self.cost_basis
+    }
+
+    /// The sum of the profits and losses realized so far, in base currency
+    #[inline(always)]
+    #[must_use]
+    pub const fn realized_pnl(&self) -> Money {
+        self.realized_pnl
+    }
+
+    /// Returns the average cost of the amount held as `ExchangeRate`, or
+    /// `None` if the position is empty or its cost basis is zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the average cost can not be represented as
+    /// `ExchangeRate` (see `ExchangeRate::try_new`).
+    pub fn average_cost(&self) -> Result<Option<ExchangeRate>, MoneyError> {
+        self.cost_basis
+            .is_positive()
+            .then(|| {
+                ExchangeRate::try_new(
+                    self.currency(),
+                    1,
+                    self.base_currency(),
+                    self.cost_basis.amount / self.quantity.amount,
+                )
+            })
+            .transpose()
+    }
+
+    /// Returns the profit (positive) or loss (negative) that would be
+    /// realized by selling the whole position at `rate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `rate` does not convert the currency of `self` into
+    /// its base currency.
+    pub fn unrealized_pnl(
+        &self,
+        rate: ExchangeRate,
+    ) -> Result<Money, MoneyError> {
+        Ok(self.value(self.quantity, rate)? - self.cost_basis)
+    }
+
+    /// Adds `amount`, bought at `rate`, to `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * `amount` <= 0
+    /// * the currency of `amount` differs from the currency of `self`
+    /// * `rate` does not convert the currency of `self` into its base
+    ///   currency
+    pub fn buy(
+        &mut self,
+        amount: Money,
+        rate: ExchangeRate,
+    ) -> Result<(), MoneyError> {
+        if !amount.is_positive() {
+            return Err(MoneyError::NotPositive(amount));
+        }
+        let cost = self.value(amount, rate)?;
+        self.quantity += amount;
+        self.cost_basis += cost;
+        match (self.method, self.lots.front_mut()) {
+            (CostMethod::WeightedAverage, Some(pool)) => {
+                pool.quantity += amount;
+                pool.cost += cost;
+            }
+            _ => self.lots.push_back(Lot {
+                quantity: amount,
+                cost,
+            }),
+        }
+        Ok(())
+    }
+
+    /// Removes `amount`, sold at `rate`, from `self` and returns the
+    /// resulting realized profit (positive) or loss (negative).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * `amount` <= 0
+    /// * the currency of `amount` differs from the currency of `self`
+    /// * `rate` does not convert the currency of `self` into its base
+    ///   currency
+    /// * `amount` exceeds the amount held
+    pub fn sell(
+        &mut self,
+        amount: Money,
+        rate: ExchangeRate,
+    ) -> Result<Money, MoneyError> {
+        if !amount.is_positive() {
+            return Err(MoneyError::NotPositive(amount));
+        }
+        let proceeds = self.value(amount, rate)?;
+        if amount.amount > self.quantity.amount {
+            return Err(MoneyError::InsufficientQuantity {
+                available: self.quantity,
+                requested: amount,
+            });
+        }
+        let minor_unit = self.base_currency().minor_unit();
+        let mut cost = Money::zero(self.base_currency());
+        let mut remaining = amount;
+        while !remaining.is_zero() {
+            let Some(lot) = (match self.method {
+                CostMethod::Lifo => self.lots.back_mut(),
+                CostMethod::Fifo | CostMethod::WeightedAverage => {
+                    self.lots.front_mut()
+                }
+            }) else {
+                // the lots always cover the quantity held
+                return Err(MoneyError::InsufficientQuantity {
+                    available: self.quantity,
+                    requested: amount,
+                });
+            };
+            if remaining.amount < lot.quantity.amount {
+                let part = Money::new(
+                    (lot.cost.amount * remaining.amount)
+                        .div_rounded(lot.quantity.amount, minor_unit),
+                    lot.cost.unit,
+                );
+                lot.quantity -= remaining;
+                lot.cost -= part;
+                cost += part;
+                remaining = Money::zero(remaining.unit);
+            } else {
+                remaining -= lot.quantity;
+                cost += lot.cost;
+                match self.method {
+                    CostMethod::Lifo => self.lots.pop_back(),
+                    CostMethod::Fifo | CostMethod::WeightedAverage => {
+                        self.lots.pop_front()
+                    }
+                };
+            }
+        }
+        self.quantity -= amount;
+        self.cost_basis -= cost;
+        let pnl = proceeds - cost;
+        self.realized_pnl += pnl;
+        Ok(pnl)
+    }
+
+    // Returns the equivalent of `amount` in base currency.
+    fn value(
+        &self,
+        amount: Money,
+        rate: ExchangeRate,
+    ) -> Result<Money, MoneyError> {
+        check_currency(self.currency(), &amount)?;
+        let value = rate.try_convert(amount)?;
+        check_currency(self.base_currency(), &value)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,260 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::format;
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Mul,
+};
+
+use fpdec::{Round, MAX_N_FRAC_DIGITS};
+use quantities::Rate;
+
+use crate::{write_padded, AmountT, Currency, Money, MoneyError, Quantity};
+
+/// Price of a unit of goods or services, given with a precision that may
+/// exceed the minor unit of its `Currency`.
+///
+/// The amount of a `Price` is rounded to the number of fractional digits
+/// given as its precision, which must be at least the minor unit of the
+/// currency. Multiplying a `Price` by a numerical quantity gives the total
+/// amount as `Money`, rounded once to the minor unit of the currency.
+///
+/// Prices per unit of a physical quantity are represented as
+/// `Rate<Money, PQ>` (see `Price::per`), so that multiplying the rate by a
+/// value of `PQ` gives the total amount as `Money`.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Dec, Decimal, Price, EUR, USD};
+/// let per_request = Price::new(Dec!(0.000012), USD, 6);
+/// assert_eq!(Dec!(1234567) * per_request, Dec!(14.81) * USD);
+/// let fuel = Price::new(Dec!(1.799), EUR, 3);
+/// assert_eq!(fuel.to_string(), "1.799 EUR");
+/// assert_eq!(fuel * Dec!(42.37), Dec!(76.22) * EUR);
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize, ::serde::Serialize)
+)]
+pub struct Price {
+    amount: AmountT,
+    currency: Currency,
+    precision: u8,
+}
+
+impl Price {
+    /// Returns a new instance of `Price`, with `amount` rounded to
+    /// `precision` fractional digits.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `precision` < `currency.minor_unit()`
+    /// * `precision` > `MAX_N_FRAC_DIGITS`
+    #[must_use]
+    pub fn new(amount: AmountT, currency: Currency, precision: u8) -> Self {
+        match Self::try_new(amount, currency, precision) {
+            Ok(price) => price,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Returns a new instance of `Price`, with `amount` rounded to
+    /// `precision` fractional digits, or an error if `precision` is out of
+    /// range.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * `precision` < `currency.minor_unit()`
+    /// * `precision` > `MAX_N_FRAC_DIGITS`
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn try_new(
+        amount: AmountT,
+        currency: Currency,
+        precision: u8,
+    ) -> Result<Self, MoneyError> {
+        if precision < currency.minor_unit() || precision > MAX_N_FRAC_DIGITS
+        {
+            return Err(MoneyError::InvalidPrecision {
+                currency,
+                precision,
+            });
+        }
+        Ok(Self {
+            amount: amount.round(precision as i8),
+            currency,
+            precision,
+        })
+    }
+
+    /// The numerical amount of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn amount(&self) -> AmountT {
+        self.amount
+    }
+
+    /// The currency of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The number of fractional digits of the amount of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Returns the amount of `self` as `Money`, i.e. rounded to the minor
+    /// unit of its currency.
+    #[inline(always)]
+    #[must_use]
+    pub fn to_money(self) -> Money {
+        Money::new(self.amount, self.currency)
+    }
+
+    /// Returns the rate equivalent to `self` being the price of
+    /// `per_unit_multiple` * `per_unit`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Price, Quantity, EUR};
+    /// # use quantities::prelude::*;
+    /// # #[quantity]
+    /// # #[ref_unit(Liter, "l", NONE, "Reference unit of quantity `Volume`")]
+    /// # #[unit(Milliliter, "ml", MILLI, 0.001, "0.001·l")]
+    /// # struct Volume {}
+    /// let fuel = Price::new(Dec!(1.799), EUR, 3).per(Dec!(1), LITER);
+    /// assert_eq!(fuel * (Dec!(42.37) * LITER), Dec!(76.22) * EUR);
+    /// assert_eq!(fuel * (Dec!(500) * MILLILITER), Dec!(0.90) * EUR);
+    /// ```
+    #[inline(always)]
+    pub const fn per<PQ: Quantity>(
+        self,
+        per_unit_multiple: AmountT,
+        per_unit: PQ::UnitType,
+    ) -> Rate<Money, PQ> {
+        Rate::new(self.amount, self.currency, per_unit_multiple, per_unit)
+    }
+
+    /// Returns the price of one `rate.per_unit()` according to `rate`,
+    /// rounded to `precision` fractional digits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * `precision` < `rate.term_unit().minor_unit()`
+    /// * `precision` > `MAX_N_FRAC_DIGITS`
+    pub fn from_rate<PQ: Quantity>(
+        rate: &Rate<Money, PQ>,
+        precision: u8,
+    ) -> Result<Self, MoneyError> {
+        Self::try_new(
+            rate.term_amount() / rate.per_unit_multiple(),
+            rate.term_unit(),
+            precision,
+        )
+    }
+}
+
+impl From<Money> for Price {
+    /// Returns a `Price` equal to `money`, with a precision equal to the
+    /// minor unit of its currency.
+    #[inline]
+    fn from(money: Money) -> Self {
+        Self {
+            amount: money.amount,
+            currency: money.unit,
+            precision: money.unit.minor_unit(),
+        }
+    }
+}
+
+impl PartialEq for Price {
+    /// Two `Price` instances are equal, if they have the same currency and
+    /// their numerical amounts are equal, regardless of their precision.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.currency == other.currency && self.amount == other.amount
+    }
+}
+
+impl Eq for Price {}
+
+impl Hash for Price {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.currency.hash(state);
+        self.amount.hash(state);
+    }
+}
+
+impl PartialOrd for Price {
+    /// Returns the numerical order of the amounts of the compared instances,
+    /// if their currencies are identical, otherwise `None`.
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.currency == other.currency)
+            .then(|| self.amount.cmp(&other.amount))
+    }
+}
+
+impl Mul<AmountT> for Price {
+    type Output = Money;
+
+    /// Returns the total amount for `rhs` units priced at `self`, rounded
+    /// to the minor unit of the currency of `self`.
+    #[inline]
+    fn mul(self, rhs: AmountT) -> Self::Output {
+        Money::new(self.amount * rhs, self.currency)
+    }
+}
+
+impl Mul<Price> for AmountT {
+    type Output = Money;
+
+    /// Returns the total amount for `self` units priced at `rhs`, rounded
+    /// to the minor unit of the currency of `rhs`.
+    #[inline(always)]
+    fn mul(self, rhs: Price) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl fmt::Display for Price {
+    /// Returns a formatted string representation of `self`.
+    ///
+    /// The amount of `self` is formatted with the precision given in the
+    /// format spec or, if none is given, with the precision of `self`, and
+    /// the resulting string is appended by a blank and the symbol of the
+    /// currency of `self`; width, fill and alignment are applied to the
+    /// resulting string.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Price, EUR};
+    /// let p = Price::new(Dec!(1.79), EUR, 3);
+    /// assert_eq!(format!("{p:>12}"), "   1.790 EUR");
+    /// ```
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prec = form.precision().unwrap_or(self.precision as usize);
+        let suffix = format!(" {}", self.currency.code());
+        write_padded(form, self.amount, Some(prec), &suffix)
+    }
+}
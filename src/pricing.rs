@@ -0,0 +1,699 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    aggregate::check_currency, AmountT, Currency, Money, MoneyError, Percent,
+    Quantity,
+};
+
+/// Quantity of an article sold at a unit price.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineItem {
+    article: String,
+    quantity: AmountT,
+    unit_price: Money,
+}
+
+impl LineItem {
+    /// Returns a new line item of `quantity` units of `article`, priced at
+    /// `unit_price`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `quantity` < 0
+    /// * `unit_price` < 0
+    #[must_use]
+    pub fn new(article: &str, quantity: AmountT, unit_price: Money) -> Self {
+        assert!(!quantity.is_negative(), "Quantity must not be negative.");
+        assert!(
+            !unit_price.is_negative(),
+            "Unit price must not be negative."
+        );
+        Self {
+            article: article.into(),
+            quantity,
+            unit_price,
+        }
+    }
+
+    /// The identifier of the article
+    #[inline(always)]
+    #[must_use]
+    pub fn article(&self) -> &str {
+        &self.article
+    }
+
+    /// The number of units
+    #[inline(always)]
+    #[must_use]
+    pub const fn quantity(&self) -> AmountT {
+        self.quantity
+    }
+
+    /// The price per unit
+    #[inline(always)]
+    #[must_use]
+    pub const fn unit_price(&self) -> Money {
+        self.unit_price
+    }
+
+    /// Returns the unit price multiplied by the quantity, rounded to the
+    /// minor unit of the currency.
+    #[inline]
+    #[must_use]
+    pub fn total(&self) -> Money {
+        self.unit_price * self.quantity
+    }
+}
+
+/// Part of an order a `PricingRule` is applied to.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum RuleScope {
+    /// The rule is applied to each line item.
+    #[default]
+    AllLines,
+    /// The rule is applied to each line item of one of the given articles.
+    Articles(Vec<String>),
+    /// The rule is applied once to the total of the order.
+    Order,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum RuleKind {
+    TieredPrice(Vec<(AmountT, Money)>),
+    PercentOff(Percent),
+    AmountOff(Money),
+    BuyXGetY { buy: u32, free: u32 },
+}
+
+/// Declarative rule used to adjust the price of line items or orders.
+///
+/// A rule is created by one of the following functions:
+/// * `PricingRule::tiered_price`: the unit price depends on the quantity
+/// * `PricingRule::percent_off`: a percentage is deducted
+/// * `PricingRule::amount_off`: a fixed amount is deducted
+/// * `PricingRule::buy_x_get_y`: some units are given for free
+///
+/// By default, a rule is applied to each line item. This can be restricted
+/// to specific articles by `PricingRule::for_articles`, or changed by
+/// `PricingRule::for_order` to apply the rule to the order total. The
+/// reduction resulting from a rule can be limited by
+/// `PricingRule::with_cap`.
+///
+/// A reduction never exceeds the amount it is applied to, i.e. prices do
+/// not become negative.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PricingRule {
+    kind: RuleKind,
+    scope: RuleScope,
+    cap: Option<Money>,
+}
+
+impl PricingRule {
+    const fn from_kind(kind: RuleKind) -> Self {
+        Self {
+            kind,
+            scope: RuleScope::AllLines,
+            cap: None,
+        }
+    }
+
+    /// Returns a rule setting the unit price of a line item according to
+    /// its quantity.
+    ///
+    /// `tiers` is a list of minimum quantities and the unit prices applying
+    /// to a quantity equal to or greater than them. The unit price of a
+    /// line item with a quantity below the first tier is not changed.
+    ///
+    /// As the tiered price replaces the price of a line item, it must be
+    /// added to `PricingRules` before any other rule. If the resulting
+    /// reduction is limited by a cap, the unit price of the line item is
+    /// not changed; only its net price is reduced by the limited amount.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `tiers` is empty
+    /// * the minimum quantities are not strictly ascending
+    /// * the unit prices have different currencies
+    /// * a unit price < 0
+    #[must_use]
+    pub fn tiered_price(tiers: &[(AmountT, Money)]) -> Self {
+        assert!(!tiers.is_empty(), "At least one tier must be given.");
+        let currency = tiers[0].1.unit;
+        for (idx, (_, price)) in tiers.iter().enumerate() {
+            assert!(
+                idx == 0 || tiers[idx - 1].0 < tiers[idx].0,
+                "Minimum quantities must be strictly ascending."
+            );
+            assert!(
+                price.unit == currency,
+                "All unit prices must have the same currency."
+            );
+            assert!(!price.is_negative(), "Unit price must not be negative.");
+        }
+        Self::from_kind(RuleKind::TieredPrice(tiers.into()))
+    }
+
+    /// Returns a rule deducting `percent` from the price.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `percent` <= 0 % or `percent` > 100 %
+    #[must_use]
+    pub fn percent_off(percent: Percent) -> Self {
+        assert!(
+            percent.value().is_positive() && percent <= Percent::HUNDRED,
+            "Percentage must be > 0 and <= 100."
+        );
+        Self::from_kind(RuleKind::PercentOff(percent))
+    }
+
+    /// Returns a rule deducting `amount` from the price.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `amount` <= 0
+    #[must_use]
+    pub fn amount_off(amount: Money) -> Self {
+        assert!(amount.is_positive(), "Amount must be > 0.");
+        Self::from_kind(RuleKind::AmountOff(amount))
+    }
+
+    /// Returns a rule giving `free` units of a line item for free for each
+    /// `buy` units paid, i.e. for every `buy + free` units of the quantity
+    /// of a line item, `free` units are deducted from its price. The free
+    /// units are valued at the net price per unit resulting from the
+    /// preceding rules.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `buy` == 0 or `free` == 0
+    #[must_use]
+    pub fn buy_x_get_y(buy: u32, free: u32) -> Self {
+        assert!(buy > 0 && free > 0, "Quantities must be > 0.");
+        Self::from_kind(RuleKind::BuyXGetY { buy, free })
+    }
+
+    /// Restricts `self` to line items of the given articles.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `self` applies to the order total
+    #[must_use]
+    pub fn for_articles(mut self, articles: &[&str]) -> Self {
+        assert!(
+            self.scope != RuleScope::Order,
+            "Rule applies to the order total."
+        );
+        self.scope = RuleScope::Articles(
+            articles.iter().map(|&article| article.into()).collect(),
+        );
+        self
+    }
+
+    /// Changes `self` to be applied to the order total instead of the line
+    /// items.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `self` is neither a percentage nor a fixed amount reduction
+    #[must_use]
+    pub fn for_order(mut self) -> Self {
+        assert!(
+            matches!(
+                self.kind,
+                RuleKind::PercentOff(_) | RuleKind::AmountOff(_)
+            ),
+            "Only percentage and fixed amount reductions can be applied to \
+             the order total."
+        );
+        self.scope = RuleScope::Order;
+        self
+    }
+
+    /// Limits the reduction resulting from `self` to `max`.
+    ///
+    /// For a rule applied to line items, `max` limits the sum of the
+    /// reductions of all line items, i.e. the lines are reduced in their
+    /// order until the cap is exhausted.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `max` <= 0
+    /// * the currency of `max` differs from the currency of the amounts of
+    ///   `self`
+    #[must_use]
+    pub fn with_cap(mut self, max: Money) -> Self {
+        assert!(max.is_positive(), "Cap must be > 0.");
+        if let Some(currency) = self.currency() {
+            assert!(
+                max.unit == currency,
+                "Cap must have the same currency as the rule."
+            );
+        }
+        self.cap = Some(max);
+        self
+    }
+
+    /// The part of an order `self` is applied to
+    #[inline(always)]
+    #[must_use]
+    pub const fn scope(&self) -> &RuleScope {
+        &self.scope
+    }
+
+    /// The maximal reduction resulting from `self`, if any
+    #[inline(always)]
+    #[must_use]
+    pub const fn cap(&self) -> Option<Money> {
+        self.cap
+    }
+
+    // Returns the currency of the amounts given for `self`, if any.
+    fn currency(&self) -> Option<Currency> {
+        match &self.kind {
+            RuleKind::TieredPrice(tiers) => Some(tiers[0].1.unit),
+            RuleKind::AmountOff(amount) => Some(amount.unit),
+            RuleKind::PercentOff(_) | RuleKind::BuyXGetY { .. } => {
+                self.cap.map(|cap| cap.unit)
+            }
+        }
+    }
+
+    fn applies_to(&self, line: &PricedLine) -> bool {
+        match &self.scope {
+            RuleScope::AllLines => true,
+            RuleScope::Articles(articles) => articles
+                .iter()
+                .any(|article| article == line.item.article()),
+            RuleScope::Order => false,
+        }
+    }
+
+    // Returns the adjustment of `line` resulting from `self`, limited to
+    // `cap` (if given), and updates the unit price of `line`, if it is
+    // changed by `self`.
+    fn adjust_line(
+        &self,
+        line: &mut PricedLine,
+        cap: Option<Money>,
+    ) -> Money {
+        let quantity = line.item.quantity;
+        let currency = line.unit_price.unit;
+        match &self.kind {
+            RuleKind::TieredPrice(tiers) => {
+                let Some((_, price)) =
+                    tiers.iter().rev().find(|(min, _)| *min <= quantity)
+                else {
+                    return Money::zero(currency);
+                };
+                // only tiered prices may precede, so the net price of the
+                // line results from its unit price
+                let amount = *price * quantity - line.net;
+                let limited = limit(amount, line.net, cap);
+                if limited == amount {
+                    line.unit_price = *price;
+                }
+                limited
+            }
+            RuleKind::BuyXGetY { buy, free } => {
+                let free_units = (quantity / (buy + free)).trunc() * *free;
+                if free_units.eq_zero() {
+                    return Money::zero(currency);
+                }
+                // the free units are valued at the net price per unit
+                let amount = Money::new(
+                    -(line.net.amount * free_units / quantity),
+                    currency,
+                );
+                limit(amount, line.net, cap)
+            }
+            _ => limit(self.reduction(line.net), line.net, cap),
+        }
+    }
+
+    // Returns the (negative) reduction of `base` resulting from `self`.
+    fn reduction(&self, base: Money) -> Money {
+        match &self.kind {
+            RuleKind::PercentOff(percent) => -(base * *percent),
+            RuleKind::AmountOff(amount) => -*amount,
+            _ => Money::zero(base.unit),
+        }
+    }
+}
+
+// Limits the reduction `amount` to `cap` (if given) and so that `base` does
+// not become negative.
+fn limit(mut amount: Money, base: Money, cap: Option<Money>) -> Money {
+    if let Some(cap) = cap {
+        if amount < -cap {
+            amount = -cap;
+        }
+    }
+    if amount < -base {
+        amount = -base;
+    }
+    amount
+}
+
+impl fmt::Display for PricingRule {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            RuleKind::TieredPrice(_) => form.write_str("tiered price")?,
+            RuleKind::PercentOff(percent) => write!(form, "{percent} off")?,
+            RuleKind::AmountOff(amount) => write!(form, "{amount} off")?,
+            RuleKind::BuyXGetY { buy, free } => {
+                write!(form, "buy {buy} get {free} free")?;
+            }
+        }
+        match &self.scope {
+            RuleScope::AllLines => {}
+            RuleScope::Articles(articles) => {
+                write!(form, " on {}", articles.join(", "))?;
+            }
+            RuleScope::Order => form.write_str(" on order")?,
+        }
+        if let Some(cap) = self.cap {
+            write!(form, ", max {cap}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Ordered list of pricing rules for orders in a specific currency.
+///
+/// The rules are applied in the order they have been added. A rule applying
+/// to line items is applied to the net price of each line item resulting
+/// from the preceding rules; a rule applying to the order total is applied
+/// to the sum of these net prices less the preceding order reductions. All
+/// adjustments are rounded to the minor unit of the currency.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{
+/// #     Dec, Decimal, LineItem, Percent, PricingRule, PricingRules, EUR,
+/// # };
+/// let rules = PricingRules::new(EUR)
+///     .with_rule(
+///         PricingRule::tiered_price(&[(Dec!(10), Dec!(1.35) * EUR)])
+///             .for_articles(&["water"]),
+///     )
+///     .with_rule(PricingRule::buy_x_get_y(2, 1).for_articles(&["soap"]))
+///     .with_rule(
+///         PricingRule::percent_off(Percent::new(Dec!(10)))
+///             .for_order()
+///             .with_cap(Dec!(2) * EUR),
+///     );
+/// let items = [
+///     LineItem::new("soap", Dec!(3), Dec!(4.99) * EUR),
+///     LineItem::new("water", Dec!(12), Dec!(1.50) * EUR),
+/// ];
+/// let pricing = rules.apply(&items)?;
+/// assert_eq!(pricing.subtotal(), Dec!(32.97) * EUR);
+/// let adjustments: Vec<_> =
+///     pricing.adjustments().iter().map(|adj| adj.amount()).collect();
+/// assert_eq!(
+///     adjustments,
+///     [Dec!(-1.80) * EUR, Dec!(-4.99) * EUR, Dec!(-2) * EUR]
+/// );
+/// assert_eq!(pricing.total(), Dec!(24.18) * EUR);
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PricingRules {
+    currency: Currency,
+    rules: Vec<PricingRule>,
+}
+
+impl PricingRules {
+    /// Returns an empty list of rules for orders in `currency`.
+    #[must_use]
+    pub const fn new(currency: Currency) -> Self {
+        Self {
+            currency,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Appends `rule` to `self`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * the currency of the amounts of `rule` differs from the currency of
+    ///   `self`
+    /// * `rule` is a tiered price and `self` contains a rule other than a
+    ///   tiered price
+    #[must_use]
+    pub fn with_rule(mut self, rule: PricingRule) -> Self {
+        if let Some(currency) = rule.currency() {
+            assert!(
+                currency == self.currency,
+                "Rule must have the same currency as the list of rules."
+            );
+        }
+        if matches!(rule.kind, RuleKind::TieredPrice(_)) {
+            assert!(
+                self.rules.iter().all(|rule| matches!(
+                    rule.kind,
+                    RuleKind::TieredPrice(_)
+                )),
+                "Tiered prices must precede all other rules."
+            );
+        }
+        self.rules.push(rule);
+        self
+    }
+
+    /// The currency of the orders `self` applies to
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The rules, in the order they are applied
+    #[inline(always)]
+    #[must_use]
+    pub fn rules(&self) -> &[PricingRule] {
+        &self.rules
+    }
+
+    /// Applies the rules of `self` to `items` and returns the resulting
+    /// prices and adjustments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the currency of the unit price of one of the items
+    /// differs from the currency of `self`.
+    pub fn apply(
+        &self,
+        items: &[LineItem],
+    ) -> Result<PricingBreakdown, MoneyError> {
+        let mut lines = Vec::with_capacity(items.len());
+        let mut subtotal = Money::zero(self.currency);
+        for item in items {
+            check_currency(self.currency, &item.unit_price)?;
+            let gross = item.total();
+            subtotal += gross;
+            lines.push(PricedLine {
+                item: item.clone(),
+                unit_price: item.unit_price,
+                gross,
+                net: gross,
+            });
+        }
+        let mut adjustments = Vec::new();
+        let mut total = subtotal;
+        for (rule_idx, rule) in self.rules.iter().enumerate() {
+            if rule.scope == RuleScope::Order {
+                let amount = limit(rule.reduction(total), total, rule.cap);
+                if !amount.is_zero() {
+                    total += amount;
+                    adjustments.push(Adjustment {
+                        rule: rule_idx,
+                        line: None,
+                        amount,
+                    });
+                }
+                continue;
+            }
+            // the cap limits the sum of the reductions of all lines
+            let mut cap = rule.cap;
+            for (line_idx, line) in lines.iter_mut().enumerate() {
+                if !rule.applies_to(line) {
+                    continue;
+                }
+                let amount = rule.adjust_line(line, cap);
+                if let Some(cap) = cap.as_mut() {
+                    if amount.is_negative() {
+                        *cap += amount;
+                    }
+                }
+                if !amount.is_zero() {
+                    line.net += amount;
+                    total += amount;
+                    adjustments.push(Adjustment {
+                        rule: rule_idx,
+                        line: Some(line_idx),
+                        amount,
+                    });
+                }
+            }
+        }
+        Ok(PricingBreakdown {
+            lines,
+            adjustments,
+            subtotal,
+            total,
+        })
+    }
+}
+
+/// Line item with the prices resulting from applying `PricingRules`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PricedLine {
+    item: LineItem,
+    unit_price: Money,
+    gross: Money,
+    net: Money,
+}
+
+impl PricedLine {
+    /// The line item priced
+    #[inline(always)]
+    #[must_use]
+    pub const fn item(&self) -> &LineItem {
+        &self.item
+    }
+
+    /// The unit price after applying tiered prices
+    ///
+    /// If the reduction resulting from a tiered price has been limited, the
+    /// unit price is not changed by this tiered price.
+    #[inline(always)]
+    #[must_use]
+    pub const fn unit_price(&self) -> Money {
+        self.unit_price
+    }
+
+    /// The total of the line item before applying the rules
+    #[inline(always)]
+    #[must_use]
+    pub const fn gross(&self) -> Money {
+        self.gross
+    }
+
+    /// The total of the line item after applying the rules
+    #[inline(always)]
+    #[must_use]
+    pub const fn net(&self) -> Money {
+        self.net
+    }
+}
+
+/// Change of a price resulting from a `PricingRule`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Adjustment {
+    rule: usize,
+    line: Option<usize>,
+    amount: Money,
+}
+
+impl Adjustment {
+    /// The index of the rule in the `PricingRules` applied
+    #[inline(always)]
+    #[must_use]
+    pub const fn rule(&self) -> usize {
+        self.rule
+    }
+
+    /// The index of the line item adjusted, or `None` if the order total
+    /// has been adjusted
+    #[inline(always)]
+    #[must_use]
+    pub const fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// The amount added to the price (negative for a reduction)
+    #[inline(always)]
+    #[must_use]
+    pub const fn amount(&self) -> Money {
+        self.amount
+    }
+}
+
+/// Result of applying `PricingRules` to line items.
+///
+/// It is guaranteed that the subtotal plus the sum of all adjustments
+/// equals the total, and that the gross amount of each line plus the sum of
+/// the adjustments of this line equals its net amount.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PricingBreakdown {
+    lines: Vec<PricedLine>,
+    adjustments: Vec<Adjustment>,
+    subtotal: Money,
+    total: Money,
+}
+
+impl PricingBreakdown {
+    /// The priced line items, in the order given
+    #[inline(always)]
+    #[must_use]
+    pub fn lines(&self) -> &[PricedLine] {
+        &self.lines
+    }
+
+    /// The adjustments, in the order they have been applied
+    #[inline(always)]
+    #[must_use]
+    pub fn adjustments(&self) -> &[Adjustment] {
+        &self.adjustments
+    }
+
+    /// Returns an iterator over the adjustments of the line item at `idx`.
+    pub fn line_adjustments(
+        &self,
+        idx: usize,
+    ) -> impl Iterator<Item = &Adjustment> {
+        self.adjustments
+            .iter()
+            .filter(move |adj| adj.line == Some(idx))
+    }
+
+    /// Returns an iterator over the adjustments of the order total.
+    pub fn order_adjustments(&self) -> impl Iterator<Item = &Adjustment> {
+        self.adjustments.iter().filter(|adj| adj.line.is_none())
+    }
+
+    /// The sum of the line items before applying the rules
+    #[inline(always)]
+    #[must_use]
+    pub const fn subtotal(&self) -> Money {
+        self.subtotal
+    }
+
+    /// The total after applying the rules
+    #[inline(always)]
+    #[must_use]
+    pub const fn total(&self) -> Money {
+        self.total
+    }
+
+    /// The sum of all adjustments, i.e. the total minus the subtotal
+    #[inline]
+    #[must_use]
+    pub fn discount(&self) -> Money {
+        self.total - self.subtotal
+    }
+}
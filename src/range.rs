@@ -0,0 +1,197 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::fmt;
+
+use crate::{aggregate::check_currency, Currency, Money, MoneyError};
+
+/// Closed range of `Money` values in a single currency, i.e. all values `v`
+/// with `min <= v <= max`.
+///
+/// Like `PartialOrd for Money`, the methods of `MoneyRange` never compare
+/// values with different currencies: such values are neither contained in a
+/// range nor do ranges in different currencies overlap.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Dec, Decimal, MoneyRange, EUR, USD};
+/// let range = MoneyRange::new(Dec!(10) * USD, Dec!(50) * USD);
+/// assert!(range.contains(&(Dec!(19.99) * USD)));
+/// assert!(!range.contains(&(Dec!(50.01) * USD)));
+/// assert!(!range.contains(&(Dec!(19.99) * EUR)));
+/// assert_eq!(range.to_string(), "10.00 USD..=50.00 USD");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize, ::serde::Serialize)
+)]
+pub struct MoneyRange {
+    min: Money,
+    max: Money,
+}
+
+impl MoneyRange {
+    /// Returns a new instance of `MoneyRange`.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * `min` and `max` have different currencies
+    /// * `min` > `max`
+    #[must_use]
+    pub fn new(min: Money, max: Money) -> Self {
+        match Self::try_new(min, max) {
+            Ok(range) => range,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Returns a new instance of `MoneyRange`, or an error if the given
+    /// bounds do not define a valid range.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` in the following cases:
+    /// * `min` and `max` have different currencies
+    /// * `min` > `max`
+    pub fn try_new(min: Money, max: Money) -> Result<Self, MoneyError> {
+        check_currency(min.unit, &max)?;
+        if min.amount > max.amount {
+            return Err(MoneyError::InvalidRange { min, max });
+        }
+        Ok(Self { min, max })
+    }
+
+    /// The lower bound of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn min(&self) -> Money {
+        self.min
+    }
+
+    /// The upper bound of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn max(&self) -> Money {
+        self.max
+    }
+
+    /// The currency of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.min.unit
+    }
+
+    /// Returns `true` if `value` has the same currency as `self` and lies
+    /// within the bounds of `self`, otherwise `false`.
+    #[must_use]
+    pub fn contains(&self, value: &Money) -> bool {
+        value.unit == self.currency()
+            && self.min.amount <= value.amount
+            && value.amount <= self.max.amount
+    }
+
+    /// Returns `value` restricted to the bounds of `self`, i.e. `self.min()`
+    /// if `value` is less than `self.min()`, `self.max()` if `value` is
+    /// greater than `self.max()`, otherwise `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `value` and `self` have different currencies.
+    pub fn clamp(&self, value: Money) -> Result<Money, MoneyError> {
+        check_currency(self.currency(), &value)?;
+        if value.amount < self.min.amount {
+            Ok(self.min)
+        } else if value.amount > self.max.amount {
+            Ok(self.max)
+        } else {
+            Ok(value)
+        }
+    }
+
+    /// Returns `true` if `self` and `other` have the same currency and have
+    /// at least one value in common, otherwise `false`.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.currency() == other.currency()
+            && self.min.amount <= other.max.amount
+            && other.min.amount <= self.max.amount
+    }
+
+    /// Returns the range of values contained in `self` and `other`, or `None`
+    /// if the two ranges do not overlap.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| Self {
+            min: if self.min.amount < other.min.amount {
+                other.min
+            } else {
+                self.min
+            },
+            max: if self.max.amount > other.max.amount {
+                other.max
+            } else {
+                self.max
+            },
+        })
+    }
+}
+
+impl fmt::Display for MoneyRange {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(form, "{}..={}", self.min, self.max)
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns `true` if `self` and `other` have the same currency as
+    /// `tolerance` and their amounts differ by no more than `tolerance`,
+    /// otherwise `false`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, EUR, USD};
+    /// let m = Dec!(100.00) * EUR;
+    /// let tolerance = Dec!(0.01) * EUR;
+    /// assert!(m.approx_eq(&(Dec!(99.99) * EUR), tolerance));
+    /// assert!(!m.approx_eq(&(Dec!(99.98) * EUR), tolerance));
+    /// assert!(!m.approx_eq(&(Dec!(100.00) * USD), tolerance));
+    /// ```
+    #[must_use]
+    pub fn approx_eq(&self, other: &Self, tolerance: Self) -> bool {
+        self.unit == other.unit
+            && self.unit == tolerance.unit
+            && (self.amount - other.amount).abs() <= tolerance.amount
+    }
+
+    /// Returns `self` restricted to the interval [`min`, `max`].
+    ///
+    /// See `MoneyRange::clamp` for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `self`, `min` and `max` do not all have the same
+    /// currency or if `min` > `max`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, EUR};
+    /// let m = Dec!(120) * EUR;
+    /// assert_eq!(m.clamp(Dec!(0) * EUR, Dec!(100) * EUR)?, Dec!(100) * EUR);
+    /// # Ok::<(), moneta::MoneyError>(())
+    /// ```
+    pub fn clamp(self, min: Self, max: Self) -> Result<Self, MoneyError> {
+        MoneyRange::try_new(min, max)?.clamp(self)
+    }
+}
@@ -0,0 +1,303 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::vec::Vec;
+
+use fpdec::{Decimal, DivRounded};
+use fpdec_core::ten_pow;
+
+use crate::{AmountT, Currency, Money, MoneyError, Percent, Quantity};
+
+/// Strategy used to round the tax amounts of an invoice.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum TaxRounding {
+    /// The tax is calculated and rounded for each line; the tax amounts of
+    /// the invoice are the sums of the rounded line amounts.
+    #[default]
+    PerLine,
+    /// The net amounts of all lines with the same tax rate are summed up;
+    /// the tax is calculated and rounded once per tax rate.
+    PerInvoice,
+}
+
+/// The amount of tax levied at a specific rate.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TaxAmount {
+    rate: Percent,
+    base: Money,
+    amount: Money,
+}
+
+impl TaxAmount {
+    /// The tax rate
+    #[inline(always)]
+    #[must_use]
+    pub const fn rate(&self) -> Percent {
+        self.rate
+    }
+
+    /// The net amount the tax is levied on
+    #[inline(always)]
+    #[must_use]
+    pub const fn base(&self) -> Money {
+        self.base
+    }
+
+    /// The amount of tax
+    #[inline(always)]
+    #[must_use]
+    pub const fn amount(&self) -> Money {
+        self.amount
+    }
+}
+
+/// Split of a money amount into net amount and tax amounts.
+///
+/// It is guaranteed that the net amount plus the sum of the tax amounts
+/// equals the gross amount, in minor units of the `Currency`.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Dec, Decimal, Percent, EUR};
+/// let vat = Percent::new(Dec!(19));
+/// let split = (Dec!(24.99) * EUR).add_tax(&[vat]);
+/// assert_eq!(split.tax(), Dec!(4.75) * EUR);
+/// assert_eq!(split.gross(), Dec!(29.74) * EUR);
+/// let split = (Dec!(29.74) * EUR).remove_tax(&[vat])?;
+/// assert_eq!(split.net(), Dec!(24.99) * EUR);
+/// # Ok::<(), moneta::MoneyError>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaxBreakdown {
+    net: Money,
+    taxes: Vec<TaxAmount>,
+    gross: Money,
+}
+
+impl TaxBreakdown {
+    /// Returns the tax breakdown of an invoice, given as lines of net amounts
+    /// and the tax rates applicable to them.
+    ///
+    /// The tax amounts are rounded according to `rounding` and are grouped
+    /// by tax rate, in order of first occurrence. A rate given more than
+    /// once for a line is applied only once.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, Percent, TaxBreakdown, TaxRounding, EUR};
+    /// let vat = [Percent::new(Dec!(19))];
+    /// let lines = [(Dec!(0.33) * EUR, &vat[..]); 3];
+    /// let split = TaxBreakdown::from_lines(lines, TaxRounding::PerLine);
+    /// assert_eq!(split.unwrap().tax(), Dec!(0.18) * EUR);
+    /// let split = TaxBreakdown::from_lines(lines, TaxRounding::PerInvoice);
+    /// assert_eq!(split.unwrap().tax(), Dec!(0.19) * EUR);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if
+    /// * `lines` is empty (`MoneyError::Empty`),
+    /// * the net amounts have different `Currency` units
+    ///   (`MoneyError::CurrencyMismatch`).
+    pub fn from_lines<'a, I>(
+        lines: I,
+        rounding: TaxRounding,
+    ) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = (Money, &'a [Percent])>,
+    {
+        let mut lines = lines.into_iter().peekable();
+        let currency = lines.peek().ok_or(MoneyError::Empty)?.0.unit();
+        let zero = Money::zero(currency);
+        let mut net = zero;
+        let mut taxes: Vec<TaxAmount> = Vec::new();
+        for (line_net, rates) in lines {
+            net = net.try_add(line_net)?;
+            for (pos, rate) in rates.iter().enumerate() {
+                if rates[..pos].contains(rate) {
+                    continue;
+                }
+                let idx = taxes
+                    .iter()
+                    .position(|t| t.rate == *rate)
+                    .unwrap_or_else(|| {
+                        taxes.push(TaxAmount {
+                            rate: *rate,
+                            base: zero,
+                            amount: zero,
+                        });
+                        taxes.len() - 1
+                    });
+                let tax = &mut taxes[idx];
+                tax.base += line_net;
+                if rounding == TaxRounding::PerLine {
+                    tax.amount += line_net * *rate;
+                }
+            }
+        }
+        if rounding == TaxRounding::PerInvoice {
+            for tax in &mut taxes {
+                tax.amount = tax.base * tax.rate;
+            }
+        }
+        Ok(Self::new(net, taxes))
+    }
+
+    fn new(net: Money, taxes: Vec<TaxAmount>) -> Self {
+        let gross = taxes.iter().fold(net, |acc, t| acc + t.amount);
+        Self { net, taxes, gross }
+    }
+
+    /// The net amount
+    #[inline(always)]
+    #[must_use]
+    pub const fn net(&self) -> Money {
+        self.net
+    }
+
+    /// The gross amount, i.e. net amount plus total tax amount
+    #[inline(always)]
+    #[must_use]
+    pub const fn gross(&self) -> Money {
+        self.gross
+    }
+
+    /// The total tax amount
+    #[inline]
+    #[must_use]
+    pub fn tax(&self) -> Money {
+        self.gross - self.net
+    }
+
+    /// The tax amounts per tax rate
+    #[inline(always)]
+    #[must_use]
+    pub fn taxes(&self) -> &[TaxAmount] {
+        &self.taxes
+    }
+
+    /// The `Currency` unit of all amounts
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.net.unit
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns the tax breakdown for `self` taken as net amount, with a tax
+    /// amount for each of the given rates.
+    ///
+    /// Each tax amount is calculated on `self` and rounded to the minor unit
+    /// of `self.unit()`.
+    #[must_use]
+    pub fn add_tax(&self, rates: &[Percent]) -> TaxBreakdown {
+        let taxes = rates
+            .iter()
+            .map(|rate| TaxAmount {
+                rate: *rate,
+                base: *self,
+                amount: *self * *rate,
+            })
+            .collect();
+        TaxBreakdown::new(*self, taxes)
+    }
+
+    /// Returns the tax breakdown for `self` taken as gross amount, with a
+    /// tax amount for each of the given rates.
+    ///
+    /// The net amount is `self` divided by 1 plus the sum of the given rates,
+    /// rounded to the minor unit of `self.unit()`. The difference between
+    /// `self` and the net amount is distributed to the tax amounts in
+    /// proportion to the rates, using the largest remainder method, so that
+    /// net and tax amounts always add up to `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::DivisionByZero)` if the given rates sum up
+    /// to -100 %.
+    pub fn remove_tax(
+        &self,
+        rates: &[Percent],
+    ) -> Result<TaxBreakdown, MoneyError> {
+        let factor = rates
+            .iter()
+            .fold(Decimal::ONE, |acc, rate| acc + rate.as_fraction());
+        if factor.eq_zero() {
+            return Err(MoneyError::DivisionByZero);
+        }
+        let net = Self::new(
+            self.amount.div_rounded(factor, self.unit.minor_unit()),
+            self.unit,
+        );
+        if rates.is_empty() {
+            return Ok(TaxBreakdown::new(net, Vec::new()));
+        }
+        let exact: Vec<AmountT> = rates
+            .iter()
+            .map(|rate| net.amount * rate.as_fraction())
+            .collect();
+        let taxes = rates
+            .iter()
+            .zip(distribute(*self - net, &exact))
+            .map(|(rate, amount)| TaxAmount {
+                rate: *rate,
+                base: net,
+                amount,
+            })
+            .collect();
+        Ok(TaxBreakdown {
+            net,
+            taxes,
+            gross: *self,
+        })
+    }
+}
+
+// Distributes `total` to parts proportional to the amounts given in
+// `exact`, using the largest remainder method: each part is the
+// corresponding exact amount rounded down to the minor unit of the currency
+// of `total`, then the remaining minor units are added to the parts with the
+// largest remainders (in order of appearance in case of equal remainders).
+// If the rounded parts exceed `total`, the surplus minor units are deducted
+// from the parts with the smallest remainders instead.
+pub(crate) fn distribute(total: Money, exact: &[AmountT]) -> Vec<Money> {
+    let currency = total.unit;
+    let minor_unit = currency.minor_unit();
+    let scale = ten_pow(minor_unit);
+    let step = Money::new(AmountT::new_raw(1, minor_unit), currency);
+    let mut parts: Vec<Money> = exact
+        .iter()
+        .map(|amount| Money::new((*amount * scale).floor() / scale, currency))
+        .collect();
+    let mut remaining = parts.iter().fold(total, |acc, part| acc - *part);
+    let mut order: Vec<usize> = (0..parts.len()).collect();
+    order.sort_by(|&a, &b| {
+        (exact[b] - parts[b].amount).cmp(&(exact[a] - parts[a].amount))
+    });
+    if remaining.is_negative() {
+        order.reverse();
+    }
+    for &idx in order.iter().cycle() {
+        if remaining.is_zero() {
+            break;
+        }
+        if remaining.is_positive() {
+            parts[idx] += step;
+            remaining -= step;
+        } else {
+            parts[idx] -= step;
+            remaining += step;
+        }
+    }
+    parts
+}
@@ -0,0 +1,425 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::format;
+use core::{
+    cmp::Ordering,
+    fmt,
+    ops::{
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign,
+    },
+};
+
+use fpdec::RoundingMode;
+
+use crate::{
+    math::round_with_mode, write_padded, AmountT, Currency, Money,
+    MoneyError, Percent, Quantity,
+};
+
+/// Money amount used as intermediate result of calculations, carrying the
+/// full precision of its numerical amount.
+///
+/// Other than `Money`, whose amount is always rounded to the minor unit of
+/// its `Currency`, `UnroundedMoney` keeps all fractional digits resulting
+/// from the operations applied to it (up to the maximum number of fractional
+/// digits supported by `Decimal`). It is converted into `Money` only by an
+/// explicit call of `UnroundedMoney::finalize` or `UnroundedMoney::round`,
+/// so that chained calculations do not accumulate rounding errors. For the
+/// same reason, its string representation is not padded to the minor unit
+/// of the currency.
+///
+/// Like `Money`, operations combining two instances require them to have
+/// the same `Currency`.
+///
+/// Example:
+///
+/// ```rust
+/// # use moneta::{Dec, Decimal, Percent, RoundingMode, EUR};
+/// let price = Dec!(19.99) * EUR;
+/// let (discount, vat) = (Percent::new(Dec!(15)), Percent::new(Dec!(19)));
+/// // rounded after each step
+/// let net = price * Dec!(3);
+/// let net = net - net * discount;
+/// assert_eq!(net + net * vat, Dec!(60.65) * EUR);
+/// // rounded only once
+/// let net = price.unrounded() * Dec!(3);
+/// let net = net - net * discount;
+/// let gross = net + net * vat;
+/// assert_eq!(gross.amount(), Dec!(60.659655));
+/// assert_eq!(gross.finalize(RoundingMode::RoundHalfUp), Dec!(60.66) * EUR);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize, ::serde::Serialize)
+)]
+pub struct UnroundedMoney {
+    amount: AmountT,
+    unit: Currency,
+}
+
+impl UnroundedMoney {
+    /// Returns a new instance of `UnroundedMoney`, without rounding
+    /// `amount`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(amount: AmountT, currency: Currency) -> Self {
+        Self {
+            amount,
+            unit: currency,
+        }
+    }
+
+    /// Returns an amount of zero in the given `Currency` unit.
+    #[inline(always)]
+    #[must_use]
+    pub const fn zero(currency: Currency) -> Self {
+        Self::new(AmountT::ZERO, currency)
+    }
+
+    /// The numerical amount of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn amount(&self) -> AmountT {
+        self.amount
+    }
+
+    /// The `Currency` unit of `self`
+    #[inline(always)]
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.unit
+    }
+
+    /// Returns `true` if the amount of `self` is equal to zero.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.amount.eq_zero()
+    }
+
+    /// Returns `true` if the amount of `self` is less than zero.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.amount.is_negative()
+    }
+
+    /// Returns `true` if the amount of `self` is greater than zero.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_positive(&self) -> bool {
+        self.amount.is_positive()
+    }
+
+    /// Returns the absolute value of `self`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn abs(&self) -> Self {
+        Self::new(self.amount.abs(), self.unit)
+    }
+
+    /// Returns the sum of `self` and `other`, if both have the same
+    /// `Currency` unit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::CurrencyMismatch)` if the operands have
+    /// different `Currency` units.
+    #[inline]
+    pub fn try_add(self, other: Self) -> Result<Self, MoneyError> {
+        self.check_same_currency(other.unit)?;
+        Ok(Self::new(self.amount + other.amount, self.unit))
+    }
+
+    /// Returns the difference of `self` and `other`, if both have the same
+    /// `Currency` unit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::CurrencyMismatch)` if the operands have
+    /// different `Currency` units.
+    #[inline]
+    pub fn try_sub(self, other: Self) -> Result<Self, MoneyError> {
+        self.check_same_currency(other.unit)?;
+        Ok(Self::new(self.amount - other.amount, self.unit))
+    }
+
+    /// Returns `self` as `Money`, with its amount rounded to the minor unit
+    /// of its `Currency` according to `mode`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, RoundingMode, UnroundedMoney, EUR, JPY};
+    /// let m = UnroundedMoney::new(Dec!(2.675), EUR);
+    /// assert_eq!(m.finalize(RoundingMode::RoundHalfEven), Dec!(2.68) * EUR);
+    /// assert_eq!(m.finalize(RoundingMode::RoundDown), Dec!(2.67) * EUR);
+    /// let m = UnroundedMoney::new(Dec!(-1234.5), JPY);
+    /// assert_eq!(m.finalize(RoundingMode::RoundHalfUp), Dec!(-1235) * JPY);
+    /// assert_eq!(m.finalize(RoundingMode::RoundCeiling), Dec!(-1234) * JPY);
+    /// ```
+    #[must_use]
+    pub fn finalize(self, mode: RoundingMode) -> Money {
+        Money::new(
+            round_with_mode(self.amount, self.unit.minor_unit(), mode),
+            self.unit,
+        )
+    }
+
+    /// Returns `self` as `Money`, with its amount rounded to the minor unit
+    /// of its `Currency` according to the default rounding mode, i.e. the
+    /// same way as done by `Money::new`.
+    #[inline(always)]
+    #[must_use]
+    pub fn round(self) -> Money {
+        Money::new(self.amount, self.unit)
+    }
+
+    #[inline]
+    fn check_same_currency(&self, other: Currency) -> Result<(), MoneyError> {
+        if self.unit == other {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch {
+                expected: self.unit,
+                found: other,
+            })
+        }
+    }
+
+    #[inline]
+    fn assert_same_currency(&self, other: Currency) {
+        if let Err(err) = self.check_same_currency(other) {
+            panic!("{err}");
+        }
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Money {
+    /// Returns `self` as `UnroundedMoney`, i.e. as starting point of a
+    /// calculation whose result is to be rounded only at the end.
+    #[inline(always)]
+    #[must_use]
+    pub const fn unrounded(self) -> UnroundedMoney {
+        UnroundedMoney::new(self.amount, self.unit)
+    }
+}
+
+impl From<Money> for UnroundedMoney {
+    #[inline(always)]
+    fn from(money: Money) -> Self {
+        money.unrounded()
+    }
+}
+
+impl PartialOrd for UnroundedMoney {
+    /// Returns the numerical order of the amounts of the compared instances,
+    /// if their `Currency` units are identical, otherwise `None`.
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.unit == other.unit).then(|| self.amount.cmp(&other.amount))
+    }
+}
+
+impl Add<Self> for UnroundedMoney {
+    type Output = Self;
+
+    /// Returns the sum of the two operands.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * The operands have different `Currency` units.
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.assert_same_currency(rhs.unit);
+        Self::new(self.amount + rhs.amount, self.unit)
+    }
+}
+
+impl Add<Money> for UnroundedMoney {
+    type Output = Self;
+
+    /// Returns the sum of the two operands.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * The operands have different `Currency` units.
+    #[inline(always)]
+    fn add(self, rhs: Money) -> Self::Output {
+        self + rhs.unrounded()
+    }
+}
+
+impl Sub<Self> for UnroundedMoney {
+    type Output = Self;
+
+    /// Returns the difference of the two operands.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * The operands have different `Currency` units.
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.assert_same_currency(rhs.unit);
+        Self::new(self.amount - rhs.amount, self.unit)
+    }
+}
+
+impl Sub<Money> for UnroundedMoney {
+    type Output = Self;
+
+    /// Returns the difference of the two operands.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * The operands have different `Currency` units.
+    #[inline(always)]
+    fn sub(self, rhs: Money) -> Self::Output {
+        self - rhs.unrounded()
+    }
+}
+
+impl Div<Self> for UnroundedMoney {
+    type Output = AmountT;
+
+    /// Returns the quotient of the two operands' amounts.
+    ///
+    /// ### Panics
+    /// The function panics in the following cases:
+    /// * The operands have different `Currency` units.
+    #[inline]
+    fn div(self, rhs: Self) -> Self::Output {
+        self.assert_same_currency(rhs.unit);
+        self.amount / rhs.amount
+    }
+}
+
+impl Mul<AmountT> for UnroundedMoney {
+    type Output = Self;
+
+    /// Returns a new instance of `UnroundedMoney`, with the same `Currency`
+    /// unit as `self` and with an amount equal to `self.amount() * rhs`.
+    #[inline(always)]
+    fn mul(self, rhs: AmountT) -> Self::Output {
+        Self::new(self.amount * rhs, self.unit)
+    }
+}
+
+impl Mul<UnroundedMoney> for AmountT {
+    type Output = UnroundedMoney;
+
+    /// Returns a new instance of `UnroundedMoney`, with the same `Currency`
+    /// unit as `rhs` and with an amount equal to `self * rhs.amount()`.
+    #[inline(always)]
+    fn mul(self, rhs: UnroundedMoney) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Div<AmountT> for UnroundedMoney {
+    type Output = Self;
+
+    /// Returns a new instance of `UnroundedMoney`, with the same `Currency`
+    /// unit as `self` and with an amount equal to `self.amount() / rhs`.
+    #[inline(always)]
+    fn div(self, rhs: AmountT) -> Self::Output {
+        Self::new(self.amount / rhs, self.unit)
+    }
+}
+
+impl Mul<Percent> for UnroundedMoney {
+    type Output = Self;
+
+    /// Returns a new instance of `UnroundedMoney`, with the same `Currency`
+    /// unit as `self` and with an amount equal to `rhs` percent of
+    /// `self.amount()`.
+    #[inline(always)]
+    fn mul(self, rhs: Percent) -> Self::Output {
+        self * rhs.as_fraction()
+    }
+}
+
+impl Neg for UnroundedMoney {
+    type Output = Self;
+
+    /// Returns a new instance of `UnroundedMoney`, with the same `Currency`
+    /// unit as `self` and with the negated amount of `self`.
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Self::new(-self.amount, self.unit)
+    }
+}
+
+impl AddAssign<Self> for UnroundedMoney {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign<Money> for UnroundedMoney {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Money) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Self> for UnroundedMoney {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl SubAssign<Money> for UnroundedMoney {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Money) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<AmountT> for UnroundedMoney {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: AmountT) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign<AmountT> for UnroundedMoney {
+    #[inline(always)]
+    fn div_assign(&mut self, rhs: AmountT) {
+        *self = *self / rhs;
+    }
+}
+
+impl fmt::Display for UnroundedMoney {
+    /// Returns a formatted string representation of `self`.
+    ///
+    /// The numerical amount of `self` is formatted with the precision given
+    /// in the format spec and appended by a blank and the symbol of the
+    /// `Currency` unit of `self`; width, fill and alignment are applied to
+    /// the resulting string.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use moneta::{Dec, Decimal, UnroundedMoney, EUR};
+    /// let m = UnroundedMoney::new(Dec!(60.659655), EUR);
+    /// assert_eq!(m.to_string(), "60.659655 EUR");
+    /// assert_eq!(format!("{m:.3}"), "60.660 EUR");
+    /// assert_eq!(format!("{m:>12.2}"), "   60.66 EUR");
+    /// ```
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suffix = format!(" {}", self.unit.code());
+        write_padded(form, self.amount, form.precision(), &suffix)
+    }
+}
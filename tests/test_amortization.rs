@@ -0,0 +1,159 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_amortization {
+    use moneta::{
+        Amortization, AmortizationSchedule, Dec, Decimal, Money, Percent,
+        Quantity, EUR, JPY,
+    };
+
+    fn check_invariants(schedule: &AmortizationSchedule, principal: Money) {
+        let mut balance = principal;
+        let mut repaid = Money::zero(principal.unit());
+        for (idx, inst) in schedule.installments().iter().enumerate() {
+            assert_eq!(inst.number() as usize, idx + 1);
+            assert_eq!(inst.payment(), inst.interest() + inst.principal());
+            balance -= inst.principal();
+            assert_eq!(inst.balance(), balance);
+            repaid += inst.principal();
+        }
+        assert_eq!(repaid, principal);
+        assert!(balance.is_zero());
+        assert_eq!(
+            schedule.total_payments(),
+            principal + schedule.total_interest()
+        );
+    }
+
+    #[test]
+    fn test_annuity() {
+        let principal = Dec!(10000) * EUR;
+        let schedule = AmortizationSchedule::annuity(
+            principal,
+            Percent::new(Dec!(6)),
+            12,
+            12,
+        );
+        assert_eq!(schedule.amortization(), Amortization::Annuity);
+        let installments = schedule.installments();
+        assert_eq!(installments.len(), 12);
+        for inst in &installments[..11] {
+            assert_eq!(inst.payment(), Dec!(860.66) * EUR);
+        }
+        let second = installments[1];
+        assert_eq!(second.interest(), Dec!(45.95) * EUR);
+        assert_eq!(second.principal(), Dec!(814.71) * EUR);
+        assert_eq!(second.balance(), Dec!(8374.63) * EUR);
+        let last = installments[11];
+        assert_eq!(last.payment(), Dec!(860.70) * EUR);
+        assert_eq!(last.interest(), Dec!(4.28) * EUR);
+        assert_eq!(last.principal(), Dec!(856.42) * EUR);
+        assert_eq!(schedule.total_interest(), Dec!(327.96) * EUR);
+        check_invariants(&schedule, principal);
+    }
+
+    #[test]
+    fn test_linear() {
+        let principal = Dec!(1000) * EUR;
+        let schedule = AmortizationSchedule::linear(
+            principal,
+            Percent::new(Dec!(5)),
+            3,
+            1,
+        );
+        assert_eq!(schedule.amortization(), Amortization::Linear);
+        let installments = schedule.installments();
+        assert_eq!(installments[0].payment(), Dec!(383.33) * EUR);
+        assert_eq!(installments[1].payment(), Dec!(366.66) * EUR);
+        assert_eq!(installments[1].interest(), Dec!(33.33) * EUR);
+        assert_eq!(installments[2].principal(), Dec!(333.34) * EUR);
+        assert_eq!(installments[2].payment(), Dec!(350.01) * EUR);
+        check_invariants(&schedule, principal);
+    }
+
+    #[test]
+    fn test_zero_rate() {
+        let principal = Dec!(1000) * EUR;
+        let schedule = AmortizationSchedule::annuity(
+            principal,
+            Percent::new(Dec!(0)),
+            3,
+            12,
+        );
+        let installments = schedule.installments();
+        assert_eq!(installments[0].payment(), Dec!(333.33) * EUR);
+        assert_eq!(installments[2].payment(), Dec!(333.34) * EUR);
+        assert!(schedule.total_interest().is_zero());
+        check_invariants(&schedule, principal);
+    }
+
+    #[test]
+    fn test_long_schedules() {
+        for principal in [Dec!(250000) * EUR, Dec!(31415926) * JPY] {
+            for amortization in [Amortization::Annuity, Amortization::Linear]
+            {
+                let schedule = AmortizationSchedule::new(
+                    amortization,
+                    principal,
+                    Percent::new(Dec!(3.85)),
+                    360,
+                    12,
+                );
+                assert_eq!(schedule.installments().len(), 360);
+                check_invariants(&schedule, principal);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounded_amount_exceeding_balance() {
+        let principal = Dec!(10) * JPY;
+        for amortization in [Amortization::Annuity, Amortization::Linear] {
+            let schedule = AmortizationSchedule::new(
+                amortization,
+                principal,
+                Percent::new(Dec!(0)),
+                12,
+                12,
+            );
+            let installments = schedule.installments();
+            assert_eq!(installments.len(), 12);
+            assert_eq!(installments[0].principal(), Dec!(1) * JPY);
+            assert!(installments[9].balance().is_zero());
+            for inst in &installments[10..] {
+                assert!(inst.payment().is_zero());
+                assert!(inst.balance().is_zero());
+            }
+            check_invariants(&schedule, principal);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_periods() {
+        let _s = AmortizationSchedule::annuity(
+            Dec!(1000) * EUR,
+            Percent::new(Dec!(5)),
+            0,
+            12,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_principal() {
+        let _s = AmortizationSchedule::linear(
+            Dec!(-1000) * EUR,
+            Percent::new(Dec!(5)),
+            12,
+            12,
+        );
+    }
+}
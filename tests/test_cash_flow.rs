@@ -0,0 +1,201 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_npv {
+    use moneta::{
+        npv, xnpv, Date, Dec, Decimal, Money, MoneyError, Percent, EUR, JPY,
+    };
+
+    #[test]
+    fn test_npv() {
+        let flows = [
+            (Date::new(2020, 1, 1), Dec!(-500000) * JPY),
+            (Date::new(2021, 1, 1), Dec!(120000) * JPY),
+            (Date::new(2022, 1, 1), Dec!(150000) * JPY),
+            (Date::new(2023, 1, 1), Dec!(180000) * JPY),
+            (Date::new(2024, 1, 1), Dec!(210000) * JPY),
+        ];
+        assert_eq!(npv(Percent::new(Dec!(8)), &flows), Ok(Dec!(36958) * JPY));
+        assert_eq!(
+            npv(Percent::new(Dec!(0)), &flows),
+            Ok(Dec!(160000) * JPY)
+        );
+        assert_eq!(
+            npv(Percent::new(Dec!(5)), &flows[..1]),
+            Ok(Dec!(-500000) * JPY)
+        );
+        // order of flows does not matter
+        let mut unsorted = flows;
+        unsorted.swap(0, 3);
+        unsorted.swap(1, 4);
+        assert_eq!(
+            npv(Percent::new(Dec!(8)), &unsorted),
+            Ok(Dec!(36958) * JPY)
+        );
+    }
+
+    #[test]
+    fn test_npv_same_date() {
+        let flows = [
+            (Date::new(2020, 1, 1), Dec!(-500) * EUR),
+            (Date::new(2020, 6, 30), Dec!(600) * EUR),
+            (Date::new(2020, 1, 1), Dec!(-500) * EUR),
+            (Date::new(2020, 6, 30), Dec!(500) * EUR),
+        ];
+        assert_eq!(npv(Percent::new(Dec!(10)), &flows), Ok(Dec!(0) * EUR));
+    }
+
+    #[test]
+    fn test_xnpv() {
+        let flows = [
+            (Date::new(2008, 1, 1), Dec!(-10000) * EUR),
+            (Date::new(2008, 3, 1), Dec!(2750) * EUR),
+            (Date::new(2008, 10, 30), Dec!(4250) * EUR),
+            (Date::new(2009, 2, 15), Dec!(3250) * EUR),
+            (Date::new(2009, 4, 1), Dec!(2750) * EUR),
+        ];
+        assert_eq!(
+            xnpv(Percent::new(Dec!(9)), &flows),
+            Ok(Dec!(2086.65) * EUR)
+        );
+        // flows are discounted to the earliest date, regardless of order
+        let mut unsorted = flows;
+        unsorted.swap(0, 2);
+        unsorted.swap(3, 4);
+        assert_eq!(
+            xnpv(Percent::new(Dec!(9)), &unsorted),
+            Ok(Dec!(2086.65) * EUR)
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let flows: Vec<(Date, Money)> = (0..=40)
+            .map(|year| (Date::new(2000 + year, 1, 1), Dec!(100) * EUR))
+            .collect();
+        assert_eq!(
+            npv(Percent::new(Dec!(-99)), &flows),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(
+            xnpv(Percent::new(Dec!(-99)), &flows),
+            Err(MoneyError::Overflow)
+        );
+        assert!(npv(Percent::new(Dec!(-99)), &flows[..5]).is_ok());
+        assert!(xnpv(Percent::new(Dec!(-99)), &flows[..5]).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_flows() {
+        assert_eq!(npv(Percent::new(Dec!(5)), &[]), Err(MoneyError::Empty));
+        assert_eq!(xnpv(Percent::new(Dec!(5)), &[]), Err(MoneyError::Empty));
+        assert_eq!(
+            npv(
+                Percent::new(Dec!(5)),
+                &[
+                    (Date::new(2024, 1, 1), Dec!(-100) * EUR),
+                    (Date::new(2025, 1, 1), Dec!(110) * JPY)
+                ]
+            ),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: JPY
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_rate() {
+        let _v = npv(
+            Percent::new(Dec!(-100)),
+            &[(Date::new(2024, 1, 1), Dec!(100) * EUR)],
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_irr {
+    use moneta::{
+        irr, xirr, Date, Dec, Decimal, MoneyError, Percent, EUR, JPY,
+    };
+
+    #[test]
+    fn test_irr() {
+        let flows = [
+            (Date::new(2020, 1, 1), Dec!(-10000) * EUR),
+            (Date::new(2021, 1, 1), Dec!(3000) * EUR),
+            (Date::new(2022, 1, 1), Dec!(4200) * EUR),
+            (Date::new(2023, 1, 1), Dec!(6800) * EUR),
+        ];
+        assert_eq!(irr(&flows), Ok(Percent::new(Dec!(16.3405600689))));
+        // order of flows does not matter
+        let mut flows = flows;
+        flows.reverse();
+        assert_eq!(irr(&flows), Ok(Percent::new(Dec!(16.3405600689))));
+    }
+
+    #[test]
+    fn test_negative_irr() {
+        let flows = [
+            (Date::new(2020, 1, 1), Dec!(-1000) * EUR),
+            (Date::new(2020, 4, 1), Dec!(400) * EUR),
+            (Date::new(2020, 7, 1), Dec!(400) * EUR),
+        ];
+        let rate = irr(&flows).unwrap();
+        assert!(rate.value() < Dec!(0));
+        assert_eq!(rate, Percent::new(Dec!(-13.6675041929)));
+    }
+
+    #[test]
+    fn test_xirr() {
+        let flows = [
+            (Date::new(2008, 1, 1), Dec!(-10000) * EUR),
+            (Date::new(2008, 3, 1), Dec!(2750) * EUR),
+            (Date::new(2008, 10, 30), Dec!(4250) * EUR),
+            (Date::new(2009, 2, 15), Dec!(3250) * EUR),
+            (Date::new(2009, 4, 1), Dec!(2750) * EUR),
+        ];
+        assert_eq!(xirr(&flows), Ok(Percent::new(Dec!(37.3362533519))));
+        // order of flows does not matter
+        let mut flows = flows;
+        flows.reverse();
+        assert_eq!(xirr(&flows), Ok(Percent::new(Dec!(37.3362533519))));
+    }
+
+    #[test]
+    fn test_no_solution() {
+        assert_eq!(
+            irr(&[
+                (Date::new(2024, 1, 1), Dec!(100) * EUR),
+                (Date::new(2025, 1, 1), Dec!(200) * EUR),
+            ]),
+            Err(MoneyError::NoConvergence)
+        );
+        assert_eq!(
+            xirr(&[
+                (Date::new(2024, 1, 1), Dec!(-100) * EUR),
+                (Date::new(2025, 1, 1), Dec!(-200) * EUR),
+            ]),
+            Err(MoneyError::NoConvergence)
+        );
+        assert_eq!(irr(&[]), Err(MoneyError::Empty));
+        assert_eq!(
+            xirr(&[
+                (Date::new(2024, 1, 1), Dec!(-100) * EUR),
+                (Date::new(2025, 1, 1), Dec!(200) * JPY),
+            ]),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: JPY
+            })
+        );
+    }
+}
@@ -0,0 +1,215 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_fee {
+    use moneta::{Dec, Decimal, Fee, Percent, EUR, USD};
+
+    #[test]
+    fn test_fee() {
+        let fee = Fee::fixed(Dec!(1.5) * EUR)
+            .with_percent(Percent::new(Dec!(0.5)))
+            .with_minimum(Dec!(3) * EUR);
+        assert_eq!(fee.currency(), EUR);
+        assert_eq!(fee.fixed_amount(), Dec!(1.5) * EUR);
+        assert_eq!(fee.percentage(), Percent::new(Dec!(0.5)));
+        assert_eq!(fee.minimum(), Dec!(3) * EUR);
+        assert_eq!(fee.amount_for(Dec!(100) * EUR), Dec!(3) * EUR);
+        assert_eq!(fee.amount_for(Dec!(1000) * EUR), Dec!(6.5) * EUR);
+        assert_eq!(fee.amount_for(Dec!(333.33) * EUR), Dec!(3.17) * EUR);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fee_minimum_diff_currency() {
+        let _f = Fee::fixed(Dec!(1.5) * EUR).with_minimum(Dec!(3) * USD);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fee_invalid_percent() {
+        let _f = Fee::percent(Percent::new(Dec!(100)), EUR);
+    }
+}
+
+#[cfg(test)]
+mod test_conversion_quote {
+    use moneta::{
+        ConversionTerms, Dec, Decimal, ExchangeRate, Fee, MoneyError,
+        Percent, Quantity, EUR, GBP, JPY, USD, VND,
+    };
+
+    fn eur_usd() -> ExchangeRate {
+        ExchangeRate::new(EUR, 1, USD, Dec!(1.08))
+    }
+
+    #[test]
+    fn test_plain_conversion() {
+        let terms = ConversionTerms::new(eur_usd());
+        let quote = terms.quote(Dec!(100) * EUR).unwrap();
+        assert_eq!(quote.source(), Dec!(100) * EUR);
+        assert!(quote.source_fee().is_zero());
+        assert_eq!(quote.mid_rate(), Dec!(1.08));
+        assert_eq!(quote.applied_rate(), Dec!(1.08));
+        assert!(quote.markup().is_zero());
+        assert!(quote.target_fee().is_zero());
+        assert_eq!(quote.target(), Dec!(100) * EUR * eur_usd());
+        // inverse direction
+        let quote = terms.quote(Dec!(108) * USD).unwrap();
+        assert_eq!(quote.target(), Dec!(100) * EUR);
+    }
+
+    #[test]
+    fn test_markup_and_fees() {
+        let terms = ConversionTerms::new(eur_usd())
+            .with_markup(Percent::new(Dec!(1)))
+            .with_fee(
+                Fee::percent(Percent::new(Dec!(1)), USD)
+                    .with_minimum(Dec!(2) * USD),
+            )
+            .with_fee(Fee::fixed(Dec!(0.5) * EUR));
+        assert_eq!(terms.rate(), eur_usd());
+        assert_eq!(terms.markup(), Percent::new(Dec!(1)));
+        assert_eq!(terms.fees().len(), 2);
+        let quote = terms.quote(Dec!(100.50) * EUR).unwrap();
+        assert_eq!(quote.source_fee(), Dec!(0.5) * EUR);
+        assert_eq!(quote.applied_rate(), Dec!(1.0692));
+        assert_eq!(quote.markup(), Dec!(1.08) * USD);
+        assert_eq!(quote.target_fee(), Dec!(2) * USD);
+        assert_eq!(quote.target(), Dec!(104.92) * USD);
+        let quote = terms.quote(Dec!(1000.50) * EUR).unwrap();
+        assert_eq!(quote.markup(), Dec!(10.80) * USD);
+        assert_eq!(quote.target_fee(), Dec!(10.69) * USD);
+        assert_eq!(quote.target(), Dec!(1058.51) * USD);
+    }
+
+    #[test]
+    fn test_fees_exceeding_amount() {
+        let terms = ConversionTerms::new(eur_usd())
+            .with_fee(Fee::fixed(Dec!(5) * EUR));
+        assert_eq!(
+            terms.quote(Dec!(3) * EUR),
+            Err(MoneyError::FeeExceedsAmount {
+                amount: Dec!(3) * EUR,
+                fee: Dec!(5) * EUR
+            })
+        );
+        let terms = ConversionTerms::new(eur_usd())
+            .with_fee(Fee::fixed(Dec!(5) * USD));
+        assert_eq!(
+            terms.quote(Dec!(3) * EUR),
+            Err(MoneyError::FeeExceedsAmount {
+                amount: Dec!(3.24) * USD,
+                fee: Dec!(5) * USD
+            })
+        );
+    }
+
+    #[test]
+    fn test_currency_mismatch() {
+        let terms = ConversionTerms::new(eur_usd());
+        assert_eq!(
+            terms.quote(Dec!(3) * GBP),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: GBP
+            })
+        );
+        assert!(terms.quote_for_target(Dec!(3) * GBP).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fee_diff_currency() {
+        let _t = ConversionTerms::new(eur_usd())
+            .with_fee(Fee::fixed(Dec!(1) * GBP));
+    }
+
+    #[test]
+    fn test_quote_for_target() {
+        let terms = ConversionTerms::new(eur_usd())
+            .with_markup(Percent::new(Dec!(1.75)))
+            .with_fee(
+                Fee::percent(Percent::new(Dec!(0.8)), USD)
+                    .with_minimum(Dec!(2) * USD),
+            )
+            .with_fee(
+                Fee::fixed(Dec!(1) * EUR)
+                    .with_percent(Percent::new(Dec!(0.3))),
+            );
+        for target in [
+            Dec!(0.01),
+            Dec!(1),
+            Dec!(99.99),
+            Dec!(100),
+            Dec!(250),
+            Dec!(1234.56),
+            Dec!(98765.43),
+        ] {
+            let target = target * USD;
+            let quote = terms.quote_for_target(target).unwrap();
+            assert!(quote.target() >= target);
+            let step = Dec!(0.01) * EUR;
+            // a smaller source amount either doesn't cover the fees or
+            // results in less than the target
+            assert!(terms
+                .quote(quote.source() - step)
+                .map_or(true, |less| less.target() < target));
+        }
+        let quote = terms.quote_for_target(Dec!(250) * EUR).unwrap();
+        assert_eq!(quote.source().unit(), USD);
+        assert!(quote.target() >= Dec!(250) * EUR);
+    }
+
+    #[test]
+    fn test_quote_for_target_no_minor_units() {
+        let rate = ExchangeRate::new(USD, 1, JPY, Dec!(151.237));
+        let terms =
+            ConversionTerms::new(rate).with_fee(Fee::fixed(Dec!(300) * JPY));
+        let quote = terms.quote_for_target(Dec!(10000) * JPY).unwrap();
+        assert_eq!(quote.source(), Dec!(68.11) * USD);
+        assert_eq!(quote.target(), Dec!(10001) * JPY);
+        let quote = terms.quote_for_target(Dec!(100) * USD).unwrap();
+        // the fee is charged in source currency here
+        assert_eq!(quote.source(), Dec!(15423) * JPY);
+        assert_eq!(quote.target(), Dec!(100) * USD);
+    }
+
+    #[test]
+    fn test_quote_for_target_small_source_unit() {
+        // one minor unit of USD is worth about 250 VND
+        let rate = ExchangeRate::new(USD, 1, VND, Dec!(25437.5));
+        let terms = ConversionTerms::new(rate)
+            .with_markup(Percent::new(Dec!(1.5)))
+            .with_fee(Fee::fixed(Dec!(0.99) * USD));
+        for target in [Dec!(0.01), Dec!(17.38), Dec!(100), Dec!(4321.09)] {
+            let target = target * USD;
+            let quote = terms.quote_for_target(target).unwrap();
+            assert_eq!(quote.source().unit(), VND);
+            assert!(quote.target() >= target);
+            let step = Dec!(1) * VND;
+            assert!(terms
+                .quote(quote.source() - step)
+                .map_or(true, |less| less.target() < target));
+        }
+    }
+
+    #[test]
+    fn test_quote_for_target_not_positive() {
+        let terms = ConversionTerms::new(eur_usd());
+        assert_eq!(
+            terms.quote_for_target(Dec!(0) * USD),
+            Err(MoneyError::NotPositive(Dec!(0) * USD))
+        );
+        assert_eq!(
+            terms.quote_for_target(Dec!(-5) * EUR),
+            Err(MoneyError::NotPositive(Dec!(-5) * EUR))
+        );
+    }
+}
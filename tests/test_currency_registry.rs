@@ -0,0 +1,376 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_scoped_registry {
+    use moneta::{
+        Currency, CurrencyRegistry, Dec, Decimal, Quantity, Unit, EUR,
+    };
+
+    #[test]
+    fn test_register_in_scoped_registry() {
+        let registry = CurrencyRegistry::new();
+        let lpa = registry.register_currency("LPA", "Points A", 0).unwrap();
+        assert_eq!(lpa.code(), "LPA");
+        assert_eq!(lpa.name_str(), "Points A");
+        assert_eq!(lpa.minor_unit(), 0);
+        assert_eq!(registry.currency_from_symbol("LPA"), Some(lpa));
+        assert!(registry.contains(&lpa));
+        assert_eq!(registry.currencies(), [lpa]);
+        assert_eq!((Dec!(17.5) * lpa).to_string(), "18 LPA");
+    }
+
+    #[test]
+    fn test_registries_are_isolated() {
+        let registry_1 = CurrencyRegistry::new();
+        let registry_2 = CurrencyRegistry::new();
+        let lpb = registry_1.register_currency("LPB", "Points B", 2).unwrap();
+        assert_eq!(registry_2.currency_from_symbol("LPB"), None);
+        assert!(!registry_2.contains(&lpb));
+        assert_eq!(
+            CurrencyRegistry::global().currency_from_symbol("LPB"),
+            None
+        );
+        assert_eq!(Currency::from_symbol("LPB"), None);
+        assert!(!Currency::iter().any(|c| c == lpb));
+    }
+
+    #[test]
+    fn test_same_symbol_in_different_registries() {
+        let registry_1 = CurrencyRegistry::new();
+        let registry_2 = CurrencyRegistry::new();
+        let lpc_1 =
+            registry_1.register_currency("LPC", "Points C", 1).unwrap();
+        let lpc_2 =
+            registry_2.register_currency("LPC", "Points C", 1).unwrap();
+        assert_ne!(lpc_1, lpc_2);
+        assert_eq!(lpc_1.code(), lpc_2.code());
+        assert!((Dec!(1) * lpc_1).try_add(Dec!(1) * lpc_2).is_err());
+    }
+
+    #[test]
+    fn test_different_definitions_in_different_registries() {
+        let registry_1 = CurrencyRegistry::new();
+        let registry_2 = CurrencyRegistry::new();
+        let lpd_1 =
+            registry_1.register_currency("LPD", "Points D", 1).unwrap();
+        let lpd_2 = registry_2.register_currency("LPD", "Points", 2).unwrap();
+        assert_eq!(lpd_1.name_str(), "Points D");
+        assert_eq!(lpd_1.minor_unit(), 1);
+        assert_eq!(lpd_2.name_str(), "Points");
+        assert_eq!(lpd_2.minor_unit(), 2);
+        assert_eq!(registry_1.currency_from_symbol("LPD"), Some(lpd_1));
+        assert_eq!(registry_2.currency_from_symbol("LPD"), Some(lpd_2));
+        assert!(registry_2.register_currency("EUR", "Euro", 3).is_err());
+    }
+
+    #[test]
+    fn test_dropped_registry() {
+        let registry = CurrencyRegistry::new();
+        let lpy = registry.register_currency("LPY", "Points Y", 0).unwrap();
+        let m = Dec!(17) * lpy;
+        assert_eq!(m.to_string(), "17 LPY");
+        drop(registry);
+        assert_eq!(lpy.code(), "???");
+        assert_eq!(m.amount(), Dec!(17));
+    }
+
+    #[test]
+    fn test_duplicate_registration() {
+        let registry = CurrencyRegistry::new();
+        let _lpe = registry.register_currency("LPE", "Points E", 0).unwrap();
+        assert!(registry.register_currency("LPE", "Points E", 0).is_err());
+    }
+
+    #[test]
+    fn test_iso_currencies() {
+        let registry = CurrencyRegistry::new();
+        assert!(registry.currencies().is_empty());
+        assert!(!registry.contains(&EUR));
+        assert_eq!(registry.currency_from_symbol("EUR"), Some(EUR));
+        assert_eq!(registry.currencies(), [EUR]);
+        let eur = registry.register_currency("EUR", "Euro", 2);
+        assert!(eur.is_err());
+        let registry = CurrencyRegistry::new();
+        assert_eq!(registry.register_currency("EUR", "Euro", 2), Ok(EUR));
+    }
+}
+
+#[cfg(test)]
+mod test_symbols {
+    use moneta::{
+        CurrencyKeyError, CurrencyRegistry, Dec, Decimal, SymbolRules,
+    };
+
+    #[test]
+    fn test_long_symbols_are_not_truncated() {
+        let registry = CurrencyRegistry::new();
+        let btc =
+            registry.register_currency("Bitcoin", "Bitcoin", 8).unwrap();
+        let bch = registry
+            .register_currency("BitcoinCash", "Bitcoin Cash", 8)
+            .unwrap();
+        assert_ne!(btc, bch);
+        assert_eq!(bch.code(), "BitcoinCash");
+        assert_eq!(registry.currency_from_symbol("BitcoinCash"), Some(bch));
+        assert_eq!((Dec!(0.5) * bch).to_string(), "0.50000000 BitcoinCash");
+    }
+
+    #[test]
+    fn test_non_ascii_symbols_are_kept() {
+        let registry = CurrencyRegistry::new();
+        let eur = registry.register_currency("€UR", "Euro Token", 2).unwrap();
+        assert_eq!(eur.code(), "€UR");
+        assert_eq!(registry.currency_from_symbol("UR"), None);
+        assert_eq!(format!("{:>6}", eur), "   €UR");
+        assert_eq!(format!("{:_>10}", Dec!(1) * eur), "__1.00 €UR");
+    }
+
+    #[test]
+    fn test_invalid_symbols() {
+        let registry = CurrencyRegistry::new();
+        assert_eq!(
+            registry.register_currency("", "Nothing", 0),
+            Err(CurrencyKeyError::Empty)
+        );
+        assert_eq!(
+            registry.register_currency("XP T", "Points", 0),
+            Err(CurrencyKeyError::InvalidChar {
+                symbol: "XP T".into(),
+                ch: ' '
+            })
+        );
+        assert!(matches!(
+            registry.register_currency("ABCDEFGHIJKLMNOPQ", "Points", 0),
+            Err(CurrencyKeyError::InvalidLength { max_len: 16, .. })
+        ));
+        assert!(registry.currencies().is_empty());
+    }
+
+    #[test]
+    fn test_registry_with_symbol_rules() {
+        let rules = SymbolRules::new(3, 3, |c| c.is_ascii_uppercase());
+        let registry = CurrencyRegistry::with_symbol_rules(rules);
+        assert_eq!(registry.symbol_rules().max_len(), 3);
+        assert!(registry.register_currency("XPT", "Points", 0).is_ok());
+        assert!(matches!(
+            registry.register_currency("XPt", "Points", 0),
+            Err(CurrencyKeyError::InvalidChar { ch: 't', .. })
+        ));
+        assert!(matches!(
+            registry.register_currency("XPTS", "Points", 0),
+            Err(CurrencyKeyError::InvalidLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_registration_errors() {
+        let registry = CurrencyRegistry::new();
+        let _lpx = registry.register_currency("LPX", "Points X", 0).unwrap();
+        assert_eq!(
+            registry.register_currency("LPX", "Points X", 0),
+            Err(CurrencyKeyError::AlreadyRegistered {
+                symbol: "LPX".into()
+            })
+        );
+        assert_eq!(
+            registry.register_currency("EUR", "Euro", 3),
+            Err(CurrencyKeyError::ConflictingDefinition {
+                symbol: "EUR".into()
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_currency_changes {
+    use std::sync::{Arc, Mutex};
+
+    use moneta::{
+        Currency, CurrencyEvent, CurrencyRegistry, Dec, Decimal, Quantity,
+        Unit, EUR,
+    };
+
+    #[test]
+    fn test_update_currency() {
+        let registry = CurrencyRegistry::new();
+        let lpf = registry.register_currency("LPF", "Points F", 0).unwrap();
+        let m = Dec!(17.5) * lpf;
+        registry.update_currency(&lpf, "Points Fine", 2).unwrap();
+        assert_eq!(lpf.name_str(), "Points Fine");
+        assert_eq!(lpf.minor_unit(), 2);
+        assert_eq!(m.amount(), Dec!(18));
+        assert_eq!(m.to_string(), "18.00 LPF");
+        assert_eq!((m / Dec!(8)).amount(), Dec!(2.25));
+    }
+
+    #[test]
+    fn test_update_iso_currency() {
+        let registry = CurrencyRegistry::new();
+        let eur = registry.currency_from_symbol("EUR").unwrap();
+        assert!(registry.update_currency(&eur, "Euro", 3).is_err());
+        assert!(EUR.update("Euro", 3).is_err());
+        assert_eq!(EUR.minor_unit(), 2);
+    }
+
+    #[test]
+    fn test_update_unregistered_currency() {
+        let registry_1 = CurrencyRegistry::new();
+        let registry_2 = CurrencyRegistry::new();
+        let lpg = registry_1.register_currency("LPG", "Points G", 0).unwrap();
+        assert!(registry_2.update_currency(&lpg, "Points", 1).is_err());
+        assert!(lpg.update("Points", 1).is_err());
+        assert_eq!(lpg.minor_unit(), 0);
+    }
+
+    #[test]
+    fn test_concurrent_cross_registry_updates() {
+        let registry_1 = CurrencyRegistry::new();
+        let registry_2 = CurrencyRegistry::new();
+        let lpo_1 =
+            registry_1.register_currency("LPO", "Points O", 0).unwrap();
+        let lpo_2 =
+            registry_2.register_currency("LPO", "Points O", 0).unwrap();
+        std::thread::scope(|scope| {
+            for (registry, curr) in
+                [(&registry_1, lpo_2), (&registry_2, lpo_1)]
+            {
+                scope.spawn(move || {
+                    for _ in 0..1000 {
+                        assert!(registry
+                            .update_currency(&curr, "Points", 1)
+                            .is_err());
+                        assert!(registry.deregister_currency(&curr).is_err());
+                    }
+                });
+            }
+        });
+        assert_eq!(lpo_1.minor_unit(), 0);
+        assert_eq!(lpo_2.minor_unit(), 0);
+    }
+
+    #[test]
+    fn test_deregister_currency() {
+        let registry = CurrencyRegistry::new();
+        let lph = registry.register_currency("LPH", "Points H", 0).unwrap();
+        let m = Dec!(17) * lph;
+        registry.deregister_currency(&lph).unwrap();
+        assert_eq!(registry.currency_from_symbol("LPH"), None);
+        assert!(!registry.contains(&lph));
+        assert!(registry.currencies().is_empty());
+        assert_eq!(m.to_string(), "17 LPH");
+        assert!(registry.deregister_currency(&lph).is_err());
+        assert!(registry.deregister_currency(&EUR).is_err());
+        // redefine
+        let lph = registry.register_currency("LPH", "Points H", 1).unwrap();
+        assert_eq!(lph.minor_unit(), 1);
+        assert_eq!(m.to_string(), "17.0 LPH");
+    }
+
+    #[test]
+    fn test_update_is_local_to_registry() {
+        let registry_1 = CurrencyRegistry::new();
+        let registry_2 = CurrencyRegistry::new();
+        let lpi_1 =
+            registry_1.register_currency("LPI", "Points I", 0).unwrap();
+        let lpi_2 =
+            registry_2.register_currency("LPI", "Points I", 0).unwrap();
+        registry_1.update_currency(&lpi_1, "Points II", 2).unwrap();
+        assert_eq!(lpi_1.minor_unit(), 2);
+        assert_eq!(lpi_2.name_str(), "Points I");
+        assert_eq!(lpi_2.minor_unit(), 0);
+        registry_1.deregister_currency(&lpi_1).unwrap();
+        assert!(registry_2.contains(&lpi_2));
+        assert!(registry_1.register_currency("LPI", "Points I", 1).is_ok());
+        assert_eq!(lpi_2.minor_unit(), 0);
+    }
+
+    #[test]
+    fn test_global_update_and_deregister() {
+        let lpj = Currency::new("LPJ", "Points J", 0).unwrap();
+        lpj.update("Points JJ", 1).unwrap();
+        assert_eq!(lpj.name(), "Points JJ");
+        assert_eq!(lpj.minor_unit(), 1);
+        lpj.deregister().unwrap();
+        assert_eq!(Currency::from_symbol("LPJ"), None);
+        assert!(lpj.deregister().is_err());
+    }
+
+    #[test]
+    fn test_notifications() {
+        let registry = CurrencyRegistry::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        registry.subscribe(move |evt| sink.lock().unwrap().push(*evt));
+        let lpk = registry.register_currency("LPK", "Points K", 0).unwrap();
+        let _eur = registry.currency_from_symbol("EUR").unwrap();
+        registry.update_currency(&lpk, "Points K", 1).unwrap();
+        assert!(registry.update_currency(&EUR, "Euro", 1).is_err());
+        registry.deregister_currency(&lpk).unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                CurrencyEvent::Registered(lpk),
+                CurrencyEvent::Updated(lpk),
+                CurrencyEvent::Deregistered(lpk)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reentrant_listener() {
+        let registry = Arc::new(CurrencyRegistry::new());
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let weak = Arc::downgrade(&registry);
+        let sink = Arc::clone(&events);
+        registry.subscribe(move |evt| {
+            let registry = weak.upgrade().unwrap();
+            if let CurrencyEvent::Registered(curr) = *evt {
+                if curr.code() == "LPL" {
+                    // register a companion currency from within the listener
+                    let lpm =
+                        registry.register_currency("LPM", "Points M", 0);
+                    registry.update_currency(&curr, "Points L", 1).unwrap();
+                    let sink = Arc::clone(&sink);
+                    registry.subscribe(move |evt| {
+                        sink.lock().unwrap().push(*evt);
+                    });
+                    assert!(lpm.is_ok());
+                }
+            }
+        });
+        let lpl = registry.register_currency("LPL", "Points", 0).unwrap();
+        let lpm = registry.currency_from_symbol("LPM").unwrap();
+        assert_eq!(lpl.name_str(), "Points L");
+        assert_eq!(lpl.minor_unit(), 1);
+        registry.deregister_currency(&lpm).unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            [CurrencyEvent::Deregistered(lpm)]
+        );
+    }
+
+    #[test]
+    fn test_repeated_redefinition() {
+        let registry = CurrencyRegistry::new();
+        let mut names = Vec::new();
+        for minor_unit in 0..4 {
+            let lpn = registry
+                .register_currency("LPN", "Points N", minor_unit)
+                .unwrap();
+            registry
+                .update_currency(&lpn, "Points NN", minor_unit)
+                .unwrap();
+            names.push(lpn.name_str());
+            registry.deregister_currency(&lpn).unwrap();
+        }
+        // the name is stored only once
+        assert!(names.iter().all(|n| n.as_ptr() == names[0].as_ptr()));
+    }
+}
@@ -48,6 +48,16 @@ mod test_custom_currency_creation {
         assert_eq!(xqd.minor_unit(), 5);
     }
 
+    #[test]
+    fn test_custom_curr_code_and_name() {
+        setup();
+        let etc = Currency::from_symbol("ETC").unwrap();
+        assert_eq!(etc.code(), "ETC");
+        assert_eq!(etc.name_str(), "Ethereum Classic");
+        assert_eq!(etc.code(), etc.symbol());
+        assert_eq!(etc.name_str(), etc.name());
+    }
+
     #[test]
     fn test_custom_curr_to_string() {
         setup();
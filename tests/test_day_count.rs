@@ -0,0 +1,194 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_date {
+    use moneta::{Date, MoneyError};
+
+    #[test]
+    fn test_date() {
+        let d = Date::new(2024, 2, 29);
+        assert_eq!(d.year(), 2024);
+        assert_eq!(d.month(), 2);
+        assert_eq!(d.day(), 29);
+        assert!(d.is_last_day_of_month());
+        assert!(!Date::new(2024, 2, 28).is_last_day_of_month());
+        assert!(Date::new(2023, 12, 31) < d);
+        assert_eq!(d.to_string(), "2024-02-29");
+        assert_eq!(Date::new(987, 6, 5).to_string(), "0987-06-05");
+    }
+
+    #[test]
+    fn test_days_until() {
+        let d1 = Date::new(2023, 12, 31);
+        let d2 = Date::new(2024, 12, 31);
+        assert_eq!(d1.days_until(&d2), 366);
+        assert_eq!(d2.days_until(&d1), -366);
+        assert_eq!(d1.days_until(&d1), 0);
+    }
+
+    #[test]
+    fn test_invalid_dates() {
+        assert_eq!(
+            Date::try_new(2023, 2, 29),
+            Err(MoneyError::InvalidDate {
+                year: 2023,
+                month: 2,
+                day: 29
+            })
+        );
+        assert!(Date::try_new(0, 1, 1).is_err());
+        assert!(Date::try_new(10000, 1, 1).is_err());
+        assert!(Date::try_new(2024, 13, 1).is_err());
+        assert!(Date::try_new(2024, 4, 31).is_err());
+        assert!(Date::try_new(2024, 4, 0).is_err());
+        assert_eq!(
+            Date::try_new(2023, 2, 29).unwrap_err().to_string(),
+            "Invalid date: 2023-02-29."
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_invalid_date() {
+        let _d = Date::new(2023, 2, 29);
+    }
+}
+
+#[cfg(test)]
+mod test_day_count {
+    use moneta::{Date, DayCount, Dec, Decimal};
+
+    #[test]
+    fn test_act_360_and_365() {
+        let start = Date::new(2024, 1, 1);
+        let end = Date::new(2025, 1, 1);
+        assert_eq!(DayCount::Act360.day_count(start, end), 366);
+        assert_eq!(
+            DayCount::Act360.year_fraction(start, end),
+            Dec!(366) / 360
+        );
+        assert_eq!(
+            DayCount::Act365Fixed.year_fraction(start, end),
+            Dec!(366) / 365
+        );
+    }
+
+    #[test]
+    fn test_thirty_360() {
+        let start = Date::new(2024, 1, 31);
+        let end = Date::new(2024, 3, 31);
+        assert_eq!(DayCount::Thirty360Us.day_count(start, end), 60);
+        assert_eq!(DayCount::Thirty360Eu.day_count(start, end), 60);
+        let start = Date::new(2024, 3, 30);
+        let end = Date::new(2024, 5, 31);
+        assert_eq!(DayCount::Thirty360Us.day_count(start, end), 60);
+        assert_eq!(DayCount::Thirty360Eu.day_count(start, end), 60);
+        let start = Date::new(2024, 3, 29);
+        assert_eq!(DayCount::Thirty360Us.day_count(start, end), 62);
+        assert_eq!(DayCount::Thirty360Eu.day_count(start, end), 61);
+    }
+
+    #[test]
+    fn test_thirty_360_end_of_february() {
+        let start = Date::new(2024, 2, 29);
+        let end = Date::new(2024, 3, 31);
+        assert_eq!(DayCount::Thirty360Us.day_count(start, end), 30);
+        assert_eq!(DayCount::Thirty360Eu.day_count(start, end), 31);
+        let start = Date::new(2023, 2, 28);
+        let end = Date::new(2024, 2, 29);
+        assert_eq!(DayCount::Thirty360Us.day_count(start, end), 360);
+        assert_eq!(DayCount::Thirty360Eu.day_count(start, end), 361);
+        assert_eq!(DayCount::Thirty360Us.year_fraction(start, end), Dec!(1));
+    }
+
+    #[test]
+    fn test_act_act_isda() {
+        let start = Date::new(2003, 11, 1);
+        let end = Date::new(2004, 5, 1);
+        assert_eq!(
+            DayCount::ActActIsda.year_fraction(start, end),
+            Dec!(61) / 365 + Dec!(121) / 366
+        );
+        let start = Date::new(2023, 7, 1);
+        let end = Date::new(2026, 7, 1);
+        assert_eq!(
+            DayCount::ActActIsda.year_fraction(start, end),
+            Dec!(184) / 365 + Dec!(2) + Dec!(181) / 365
+        );
+        let start = Date::new(2024, 1, 1);
+        let end = Date::new(2025, 1, 1);
+        assert_eq!(DayCount::ActActIsda.year_fraction(start, end), Dec!(1));
+        let end = Date::new(2024, 7, 1);
+        assert_eq!(
+            DayCount::ActActIsda.year_fraction(start, end),
+            Dec!(182) / 366
+        );
+    }
+
+    #[test]
+    fn test_reversed_dates() {
+        let start = Date::new(2023, 11, 15);
+        let end = Date::new(2024, 2, 29);
+        for dc in [
+            DayCount::Act360,
+            DayCount::Act365Fixed,
+            DayCount::Thirty360Us,
+            DayCount::Thirty360Eu,
+            DayCount::ActActIsda,
+        ] {
+            assert_eq!(
+                dc.year_fraction(end, start),
+                -dc.year_fraction(start, end)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_accrue {
+    use moneta::{Date, DayCount, Dec, Decimal, Percent, EUR, JPY};
+
+    #[test]
+    fn test_accrue() {
+        let m = Dec!(100000) * EUR;
+        let rate = Percent::new(Dec!(3.5));
+        let start = Date::new(2024, 1, 15);
+        let end = Date::new(2024, 4, 15);
+        assert_eq!(
+            m.accrue(rate, start, end, DayCount::Act360),
+            Dec!(884.72) * EUR
+        );
+        assert_eq!(
+            m.accrue(rate, start, end, DayCount::Act365Fixed),
+            Dec!(872.60) * EUR
+        );
+        assert_eq!(
+            m.accrue(rate, start, end, DayCount::Thirty360Us),
+            Dec!(875) * EUR
+        );
+        assert_eq!(
+            m.accrue(rate, start, end, DayCount::ActActIsda),
+            Dec!(870.22) * EUR
+        );
+        assert!(m.accrue(rate, start, start, DayCount::Act360).is_zero());
+    }
+
+    #[test]
+    fn test_accrue_rounding() {
+        let m = Dec!(1234567) * JPY;
+        let rate = Percent::new(Dec!(0.1));
+        let start = Date::new(2024, 1, 1);
+        let end = Date::new(2024, 1, 31);
+        assert_eq!(
+            m.accrue(rate, start, end, DayCount::Act365Fixed),
+            Dec!(101) * JPY
+        );
+    }
+}
@@ -56,6 +56,35 @@ mod test_money_exchange {
     }
 }
 
+#[cfg(test)]
+mod test_try_new {
+    use moneta::{Dec, Decimal, ExchangeRate, MoneyError, EUR, USD};
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            ExchangeRate::try_new(USD, 1, EUR, Dec!(0.98078)),
+            Ok(ExchangeRate::new(USD, 1, EUR, Dec!(0.98078)))
+        );
+        assert_eq!(
+            ExchangeRate::try_new(USD, 1, USD, Dec!(1)),
+            Err(MoneyError::IdenticalRateCurrencies(USD))
+        );
+        assert_eq!(
+            ExchangeRate::try_new(USD, 0, EUR, Dec!(1)),
+            Err(MoneyError::InvalidUnitMultiple(0))
+        );
+        assert_eq!(
+            ExchangeRate::try_new(USD, 1, EUR, Dec!(-0.5)),
+            Err(MoneyError::InvalidTermAmount(Dec!(-0.5)))
+        );
+        assert_eq!(
+            ExchangeRate::try_new(USD, 1, EUR, Dec!(0.0000000000001)),
+            Err(MoneyError::InvalidUnitMultiple(1))
+        );
+    }
+}
+
 #[cfg(test)]
 mod test_triangulation {
     use moneta::{Dec, Decimal, ExchangeRate, EUR, HKD, USD};
@@ -127,3 +156,200 @@ mod test_triangulation {
         let _r = usd_2_eur / usd_2_eur;
     }
 }
+
+#[cfg(test)]
+mod test_fx_gain_loss {
+    use moneta::{
+        Dec, Decimal, ExchangeRate, MoneyError, EUR, GBP, JPY, USD,
+    };
+
+    #[test]
+    fn test_realized() {
+        let receivable = Dec!(1000) * USD;
+        let booking_rate = ExchangeRate::new(USD, 1, EUR, Dec!(0.9132));
+        let settlement_rate = ExchangeRate::new(USD, 1, EUR, Dec!(0.9325));
+        assert_eq!(
+            receivable.realized_fx_gain_loss(booking_rate, settlement_rate),
+            Ok(Dec!(19.30) * EUR)
+        );
+        assert_eq!(
+            (-receivable)
+                .realized_fx_gain_loss(booking_rate, settlement_rate),
+            Ok(Dec!(-19.30) * EUR)
+        );
+        assert_eq!(
+            receivable.realized_fx_gain_loss(booking_rate, booking_rate),
+            Ok(Dec!(0) * EUR)
+        );
+    }
+
+    #[test]
+    fn test_unrealized() {
+        let item = Dec!(1234.56) * USD;
+        let booking_rate = ExchangeRate::new(USD, 1, JPY, Dec!(151.237));
+        let closing_rate = ExchangeRate::new(USD, 1, JPY, Dec!(148.5));
+        assert_eq!(
+            item.unrealized_fx_gain_loss(booking_rate, closing_rate),
+            Ok(Dec!(-3379) * JPY)
+        );
+    }
+
+    #[test]
+    fn test_inverse_quotations() {
+        let receivable = Dec!(1000) * USD;
+        // EUR is the unit currency of the booking rate
+        let booking_rate = ExchangeRate::new(EUR, 1, USD, Dec!(1.1));
+        let closing_rate = ExchangeRate::new(USD, 1, EUR, Dec!(0.9));
+        assert_eq!(
+            receivable.unrealized_fx_gain_loss(booking_rate, closing_rate),
+            Ok(Dec!(-9.09) * EUR)
+        );
+        assert_eq!(
+            receivable.unrealized_fx_gain_loss(
+                booking_rate,
+                closing_rate.inverted()
+            ),
+            receivable.unrealized_fx_gain_loss(
+                booking_rate.inverted(),
+                closing_rate
+            ),
+        );
+    }
+
+    #[test]
+    fn test_currency_mismatch() {
+        let receivable = Dec!(1000) * USD;
+        let usd_eur = ExchangeRate::new(USD, 1, EUR, Dec!(0.9132));
+        let usd_gbp = ExchangeRate::new(USD, 1, GBP, Dec!(0.7895));
+        let eur_gbp = ExchangeRate::new(EUR, 1, GBP, Dec!(0.8645));
+        assert_eq!(
+            receivable.realized_fx_gain_loss(usd_eur, usd_gbp),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: GBP
+            })
+        );
+        assert_eq!(
+            receivable.unrealized_fx_gain_loss(usd_eur, eur_gbp),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: USD
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_source_for_target {
+    use moneta::{
+        Dec, Decimal, ExchangeRate, Money, MoneyError, Quantity,
+        TargetRounding, EUR, GBP, JPY, USD,
+    };
+
+    fn check(rate: ExchangeRate, source_step: Money, target: Money) {
+        let convert = |source: Money| {
+            if target.unit() == rate.term_currency() {
+                source * rate
+            } else {
+                source / rate
+            }
+        };
+        let source = rate
+            .source_for_target(target, TargetRounding::AtLeast)
+            .unwrap();
+        assert!(convert(source) >= target);
+        assert!(convert(source - source_step) < target);
+        let source = rate
+            .source_for_target(target, TargetRounding::AtMost)
+            .unwrap();
+        assert!(convert(source) <= target);
+        assert!(convert(source + source_step) > target);
+    }
+
+    #[test]
+    fn test_exact_target() {
+        let rate = ExchangeRate::new(USD, 1, EUR, Dec!(0.9683));
+        let target = Dec!(100) * EUR;
+        let source = rate
+            .source_for_target(target, TargetRounding::AtLeast)
+            .unwrap();
+        assert_eq!(source, Dec!(103.27) * USD);
+        assert_eq!(source * rate, target);
+        assert_eq!(
+            rate.source_for_target(target, TargetRounding::AtMost),
+            Ok(source)
+        );
+    }
+
+    #[test]
+    fn test_unreachable_target() {
+        let rate = ExchangeRate::new(USD, 1, JPY, Dec!(151.237));
+        let target = Dec!(10002) * JPY;
+        assert_eq!(
+            rate.source_for_target(target, TargetRounding::AtLeast),
+            Ok(Dec!(66.14) * USD)
+        );
+        assert_eq!(
+            rate.source_for_target(target, TargetRounding::AtMost),
+            Ok(Dec!(66.13) * USD)
+        );
+    }
+
+    #[test]
+    fn test_multiple_sources() {
+        // several amounts of JPY convert to the same amount of USD
+        let rate = ExchangeRate::new(USD, 1, JPY, Dec!(151.237));
+        let target = Dec!(100) * USD;
+        assert_eq!(
+            rate.source_for_target(target, TargetRounding::AtLeast),
+            Ok(Dec!(15123) * JPY)
+        );
+        assert_eq!(
+            rate.source_for_target(target, TargetRounding::AtMost),
+            Ok(Dec!(15124) * JPY)
+        );
+    }
+
+    #[test]
+    fn test_various_targets() {
+        let rates = [
+            ExchangeRate::new(USD, 1, EUR, Dec!(0.9683)),
+            ExchangeRate::new(EUR, 1, USD, Dec!(1.0725)),
+            ExchangeRate::new(USD, 1, JPY, Dec!(151.237)),
+            ExchangeRate::new(GBP, 1000, JPY, Dec!(0.000172)),
+        ];
+        let targets = [
+            Dec!(-250.75),
+            Dec!(0),
+            Dec!(0.01),
+            Dec!(1),
+            Dec!(99.99),
+            Dec!(100.005),
+            Dec!(1234.56),
+            Dec!(98765.43),
+        ];
+        for rate in rates {
+            let (unit, term) = (rate.unit_currency(), rate.term_currency());
+            for target in targets {
+                let unit_step =
+                    Money::new(Decimal::new_raw(1, unit.minor_unit()), unit);
+                let term_step =
+                    Money::new(Decimal::new_raw(1, term.minor_unit()), term);
+                check(rate, unit_step, Money::new(target, term));
+                check(rate, term_step, Money::new(target, unit));
+            }
+        }
+    }
+
+    #[test]
+    fn test_currency_mismatch() {
+        let rate = ExchangeRate::new(USD, 1, EUR, Dec!(0.9683));
+        assert_eq!(
+            rate.source_for_target(Dec!(3) * GBP, TargetRounding::AtLeast),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: GBP
+            })
+        );
+    }
+}
@@ -0,0 +1,100 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_interest {
+    use moneta::{
+        Compounding, Dec, Decimal, InterestRate, InterestRounding, Percent,
+        EUR, JPY,
+    };
+
+    #[test]
+    fn test_periods_per_year() {
+        assert_eq!(Compounding::Simple.periods_per_year(), None);
+        assert_eq!(Compounding::Quarterly.periods_per_year(), Some(4));
+        assert_eq!(Compounding::Daily.periods_per_year(), Some(365));
+        assert_eq!(Compounding::PerYear(52).periods_per_year(), Some(52));
+    }
+
+    #[test]
+    fn test_simple_interest() {
+        let rate =
+            InterestRate::new(Percent::new(Dec!(5)), Compounding::Simple);
+        let principal = Dec!(1000) * EUR;
+        assert_eq!(rate.growth_factor(Dec!(2.5)), Dec!(1.125));
+        assert_eq!(rate.future_value(principal, Dec!(2.5)), Dec!(1125) * EUR);
+        assert_eq!(rate.interest(principal, Dec!(0.5)), Dec!(25) * EUR);
+        assert!(rate.interest(principal, Dec!(0)).is_zero());
+    }
+
+    #[test]
+    fn test_compound_interest() {
+        let principal = Dec!(1000) * EUR;
+        let rate =
+            InterestRate::new(Percent::new(Dec!(5)), Compounding::Annually);
+        assert_eq!(rate.future_value(principal, Dec!(2)), Dec!(1102.5) * EUR);
+        // remaining half year accrues simple interest
+        assert_eq!(
+            rate.future_value(principal, Dec!(2.5)),
+            Dec!(1130.06) * EUR
+        );
+        let rate =
+            InterestRate::new(Percent::new(Dec!(8)), Compounding::Quarterly);
+        assert_eq!(rate.growth_factor(Dec!(1)), Dec!(1.08243216));
+        assert_eq!(rate.interest(principal, Dec!(1)), Dec!(82.43) * EUR);
+    }
+
+    #[test]
+    fn test_rounding_per_period() {
+        let principal = Dec!(1000) * JPY;
+        let rate =
+            InterestRate::new(Percent::new(Dec!(5)), Compounding::Monthly);
+        assert_eq!(rate.rounding(), InterestRounding::Final);
+        assert_eq!(rate.future_value(principal, Dec!(1)), Dec!(1051) * JPY);
+        let rate = rate.with_rounding(InterestRounding::PerPeriod);
+        assert_eq!(rate.rounding(), InterestRounding::PerPeriod);
+        assert_eq!(rate.future_value(principal, Dec!(1)), Dec!(1048) * JPY);
+        assert_eq!(rate.interest(principal, Dec!(1.5)), Dec!(72) * JPY);
+    }
+
+    #[test]
+    fn test_many_periods() {
+        let rate =
+            InterestRate::new(Percent::new(Dec!(3)), Compounding::Daily);
+        let principal = Dec!(25000) * EUR;
+        let fv = rate.future_value(principal, Dec!(30));
+        assert_eq!(fv, Dec!(61487.80) * EUR);
+        let rate = rate.with_rounding(InterestRounding::PerPeriod);
+        let diff = (rate.future_value(principal, Dec!(30)) - fv).abs();
+        assert!(diff < Dec!(10) * EUR);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_periods_per_year() {
+        let _r =
+            InterestRate::new(Percent::new(Dec!(5)), Compounding::PerYear(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_period() {
+        let rate =
+            InterestRate::new(Percent::new(Dec!(5)), Compounding::Annually);
+        let _m = rate.future_value(Dec!(1000) * EUR, Dec!(-1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_too_many_periods() {
+        let rate =
+            InterestRate::new(Percent::new(Dec!(5)), Compounding::Monthly);
+        let _f = rate.growth_factor(Dec!(2000000000000000000));
+    }
+}
@@ -0,0 +1,275 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_invoice_line {
+    use moneta::{Dec, Decimal, InvoiceLine, Percent, EUR};
+
+    #[test]
+    fn test_invoice_line() {
+        let vat = Percent::new(Dec!(19));
+        let line = InvoiceLine::new("tea", Dec!(2.5), Dec!(3.99) * EUR, vat);
+        assert_eq!(line.description(), "tea");
+        assert_eq!(line.quantity(), Dec!(2.5));
+        assert_eq!(line.unit_price(), Dec!(3.99) * EUR);
+        assert_eq!(line.tax_rate(), vat);
+    }
+}
+
+#[cfg(test)]
+mod test_invoice {
+    use moneta::{
+        Dec, Decimal, Invoice, InvoiceLine, InvoiceTotals, Money, MoneyError,
+        Percent, TaxRounding, EUR, USD,
+    };
+
+    fn check_reconciled(totals: &InvoiceTotals, invoice: &Invoice) {
+        let zero = Money::zero(invoice.currency());
+        for subtotal in totals.subtotals() {
+            let (net, tax) = invoice
+                .lines()
+                .iter()
+                .zip(totals.lines())
+                .filter(|(line, _)| line.tax_rate() == subtotal.rate())
+                .fold((zero, zero), |(net, tax), (_, amounts)| {
+                    (net + amounts.net(), tax + amounts.tax())
+                });
+            assert_eq!(net, subtotal.base());
+            assert_eq!(tax, subtotal.amount());
+        }
+        let (net, tax) = totals
+            .lines()
+            .iter()
+            .fold((zero, zero), |acc, l| (acc.0 + l.net(), acc.1 + l.tax()));
+        assert_eq!(net, totals.net());
+        assert_eq!(tax, totals.tax());
+        assert_eq!(net + tax, totals.gross());
+    }
+
+    #[test]
+    fn test_new() {
+        let invoice = Invoice::new(USD, TaxRounding::PerInvoice);
+        assert_eq!(invoice.currency(), USD);
+        assert_eq!(invoice.rounding(), TaxRounding::PerInvoice);
+        assert!(invoice.lines().is_empty());
+    }
+
+    #[test]
+    fn test_add_line() {
+        let vat = Percent::new(Dec!(19));
+        let mut invoice = Invoice::new(EUR, TaxRounding::PerLine);
+        let line = InvoiceLine::new("tea", Dec!(2), Dec!(3.99) * EUR, vat);
+        assert!(invoice.add_line(line.clone()).is_ok());
+        assert_eq!(invoice.lines(), &[line]);
+        let line = InvoiceLine::new("tea", Dec!(2), Dec!(3.99) * USD, vat);
+        assert_eq!(
+            invoice.add_line(line),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: USD
+            })
+        );
+        assert_eq!(invoice.lines().len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_line_currency_mismatch() {
+        let vat = Percent::new(Dec!(19));
+        let _i = Invoice::new(EUR, TaxRounding::PerLine).with_line(
+            InvoiceLine::new("tea", Dec!(2), Dec!(3.99) * USD, vat),
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        for rounding in [TaxRounding::PerLine, TaxRounding::PerInvoice] {
+            let totals = Invoice::new(EUR, rounding).totals();
+            assert!(totals.lines().is_empty());
+            assert!(totals.subtotals().is_empty());
+            assert_eq!(totals.net(), Money::zero(EUR));
+            assert_eq!(totals.tax(), Money::zero(EUR));
+            assert_eq!(totals.gross(), Money::zero(EUR));
+        }
+    }
+
+    #[test]
+    fn test_per_line() {
+        let reduced = Percent::new(Dec!(7));
+        let standard = Percent::new(Dec!(19));
+        let invoice = Invoice::new(EUR, TaxRounding::PerLine)
+            .with_line(InvoiceLine::new(
+                "bread",
+                Dec!(3),
+                Dec!(2.49) * EUR,
+                reduced,
+            ))
+            .with_line(InvoiceLine::new(
+                "soap",
+                Dec!(0.5),
+                Dec!(1.35) * EUR,
+                standard,
+            ))
+            .with_line(InvoiceLine::new(
+                "milk",
+                Dec!(1.5),
+                Dec!(1.09) * EUR,
+                reduced,
+            ));
+        let totals = invoice.totals();
+        let lines = totals.lines();
+        assert_eq!(lines[0].net(), Dec!(7.47) * EUR);
+        assert_eq!(lines[0].tax(), Dec!(0.52) * EUR);
+        assert_eq!(lines[0].gross(), Dec!(7.99) * EUR);
+        assert_eq!(lines[1].net(), Dec!(0.68) * EUR);
+        assert_eq!(lines[1].tax(), Dec!(0.13) * EUR);
+        assert_eq!(lines[2].net(), Dec!(1.64) * EUR);
+        assert_eq!(lines[2].tax(), Dec!(0.11) * EUR);
+        let subtotals = totals.subtotals();
+        assert_eq!(subtotals.len(), 2);
+        assert_eq!(subtotals[0].rate(), reduced);
+        assert_eq!(subtotals[0].base(), Dec!(9.11) * EUR);
+        assert_eq!(subtotals[0].amount(), Dec!(0.63) * EUR);
+        assert_eq!(subtotals[1].rate(), standard);
+        assert_eq!(subtotals[1].base(), Dec!(0.68) * EUR);
+        assert_eq!(subtotals[1].amount(), Dec!(0.13) * EUR);
+        assert_eq!(totals.net(), Dec!(9.79) * EUR);
+        assert_eq!(totals.tax(), Dec!(0.76) * EUR);
+        assert_eq!(totals.gross(), Dec!(10.55) * EUR);
+        assert_eq!(totals.tax_breakdown().currency(), EUR);
+        check_reconciled(&totals, &invoice);
+    }
+
+    #[test]
+    fn test_per_invoice_distribution() {
+        let vat = Percent::new(Dec!(19));
+        let line = InvoiceLine::new("pin", Dec!(0.333), Dec!(1) * EUR, vat);
+        let invoice = Invoice::new(EUR, TaxRounding::PerInvoice)
+            .with_line(line.clone())
+            .with_line(line.clone())
+            .with_line(line);
+        let totals = invoice.totals();
+        let lines = totals.lines();
+        assert_eq!(lines[0].net(), Dec!(0.34) * EUR);
+        assert_eq!(lines[1].net(), Dec!(0.33) * EUR);
+        assert_eq!(lines[2].net(), Dec!(0.33) * EUR);
+        assert_eq!(lines[0].tax(), Dec!(0.07) * EUR);
+        assert_eq!(lines[1].tax(), Dec!(0.06) * EUR);
+        assert_eq!(lines[2].tax(), Dec!(0.06) * EUR);
+        assert_eq!(totals.net(), Dec!(1.00) * EUR);
+        assert_eq!(totals.tax(), Dec!(0.19) * EUR);
+        assert_eq!(totals.gross(), Dec!(1.19) * EUR);
+        check_reconciled(&totals, &invoice);
+    }
+
+    #[test]
+    fn test_per_line_vs_per_invoice() {
+        let vat = Percent::new(Dec!(19));
+        let line = InvoiceLine::new("pin", Dec!(0.333), Dec!(1) * EUR, vat);
+        let mut per_line = Invoice::new(EUR, TaxRounding::PerLine);
+        let mut per_invoice = Invoice::new(EUR, TaxRounding::PerInvoice);
+        for _ in 0..3 {
+            per_line.add_line(line.clone()).unwrap();
+            per_invoice.add_line(line.clone()).unwrap();
+        }
+        let totals = per_line.totals();
+        assert_eq!(totals.net(), Dec!(0.99) * EUR);
+        assert_eq!(totals.tax(), Dec!(0.18) * EUR);
+        check_reconciled(&totals, &per_line);
+        let totals = per_invoice.totals();
+        assert_eq!(totals.net(), Dec!(1.00) * EUR);
+        assert_eq!(totals.tax(), Dec!(0.19) * EUR);
+        check_reconciled(&totals, &per_invoice);
+    }
+
+    #[test]
+    fn test_per_invoice_mixed_rates() {
+        let reduced = Percent::new(Dec!(7));
+        let standard = Percent::new(Dec!(19));
+        let mut invoice = Invoice::new(EUR, TaxRounding::PerInvoice);
+        for (idx, qty) in [
+            Dec!(0.125),
+            Dec!(3.3),
+            Dec!(7),
+            Dec!(0.45),
+            Dec!(1.75),
+            Dec!(2.2),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let rate = if idx % 2 == 0 { reduced } else { standard };
+            invoice
+                .add_line(InvoiceLine::new("x", qty, Dec!(1.38) * EUR, rate))
+                .unwrap();
+        }
+        let totals = invoice.totals();
+        let subtotals = totals.subtotals();
+        assert_eq!(subtotals.len(), 2);
+        assert_eq!(subtotals[0].rate(), reduced);
+        assert_eq!(subtotals[0].base(), Dec!(12.25) * EUR);
+        assert_eq!(subtotals[0].amount(), Dec!(0.86) * EUR);
+        assert_eq!(subtotals[1].rate(), standard);
+        assert_eq!(subtotals[1].base(), Dec!(8.21) * EUR);
+        assert_eq!(subtotals[1].amount(), Dec!(1.56) * EUR);
+        assert_eq!(totals.net(), Dec!(20.46) * EUR);
+        assert_eq!(totals.tax(), Dec!(2.42) * EUR);
+        assert_eq!(totals.gross(), Dec!(22.88) * EUR);
+        check_reconciled(&totals, &invoice);
+    }
+
+    #[test]
+    fn test_per_invoice_credit_line() {
+        let vat = Percent::new(Dec!(19));
+        let invoice = Invoice::new(EUR, TaxRounding::PerInvoice)
+            .with_line(InvoiceLine::new(
+                "goods",
+                Dec!(4.5),
+                Dec!(3.33) * EUR,
+                vat,
+            ))
+            .with_line(InvoiceLine::credit(
+                "returned",
+                Dec!(1.5),
+                Dec!(3.33) * EUR,
+                vat,
+            ));
+        let totals = invoice.totals();
+        assert_eq!(totals.net(), Dec!(9.99) * EUR);
+        assert_eq!(totals.tax(), Dec!(1.90) * EUR);
+        assert_eq!(totals.lines()[1].net(), Dec!(-5.00) * EUR);
+        let credit = &invoice.lines()[1];
+        assert!(credit.is_credit());
+        assert_eq!(credit.quantity(), Dec!(-1.5));
+        assert_eq!(credit.item().quantity(), Dec!(1.5));
+        check_reconciled(&totals, &invoice);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_quantity() {
+        let _l = InvoiceLine::new(
+            "returned",
+            Dec!(-1.5),
+            Dec!(3.33) * EUR,
+            Percent::new(Dec!(19)),
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_credit_price() {
+        let _l = InvoiceLine::credit(
+            "discount",
+            Dec!(1),
+            Dec!(-5) * EUR,
+            Percent::new(Dec!(19)),
+        );
+    }
+}
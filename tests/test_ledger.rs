@@ -0,0 +1,266 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_journal_entry {
+    use moneta::{
+        Date, Dec, Decimal, JournalEntry, MoneyError, Side, EUR, USD,
+    };
+
+    #[test]
+    fn test_postings() {
+        let entry = JournalEntry::new(Date::new(2024, 3, 1), "Rent")
+            .debit("6310", Dec!(1200) * EUR)
+            .credit("1200", Dec!(1200) * EUR);
+        assert_eq!(entry.date(), Date::new(2024, 3, 1));
+        assert_eq!(entry.description(), "Rent");
+        let postings = entry.postings();
+        assert_eq!(postings.len(), 2);
+        assert_eq!(postings[0].account(), "6310");
+        assert_eq!(postings[0].side(), Side::Debit);
+        assert_eq!(postings[1].side(), Side::Credit);
+        assert_eq!(postings[1].amount(), Dec!(1200) * EUR);
+        assert!(entry.check_balance().is_ok());
+    }
+
+    #[test]
+    fn test_multi_currency_entry() {
+        let entry = JournalEntry::new(Date::new(2024, 3, 1), "FX")
+            .debit("1210", Dec!(108.50) * USD)
+            .credit("1790", Dec!(108.50) * USD)
+            .debit("1790", Dec!(100) * EUR)
+            .credit("1200", Dec!(100) * EUR);
+        assert!(entry.check_balance().is_ok());
+    }
+
+    #[test]
+    fn test_unbalanced_entries() {
+        let date = Date::new(2024, 3, 1);
+        assert_eq!(
+            JournalEntry::new(date, "Nothing").check_balance(),
+            Err(MoneyError::EmptyEntry)
+        );
+        let entry = JournalEntry::new(date, "Typo")
+            .debit("6310", Dec!(1200) * EUR)
+            .credit("1200", Dec!(1020) * EUR);
+        let err = entry.check_balance().unwrap_err();
+        assert_eq!(
+            err,
+            MoneyError::UnbalancedEntry {
+                debit: Dec!(1200) * EUR,
+                credit: Dec!(1020) * EUR
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Journal entry is unbalanced: debit 1200.00 EUR, credit 1020.00 \
+             EUR."
+        );
+        // balanced in total, but not per currency
+        let entry = JournalEntry::new(date, "Mixed")
+            .debit("1210", Dec!(100) * USD)
+            .credit("1200", Dec!(100) * EUR);
+        assert!(matches!(
+            entry.check_balance(),
+            Err(MoneyError::UnbalancedEntry { .. })
+        ));
+        let entry = JournalEntry::new(date, "Negative")
+            .debit("6310", Dec!(-10) * EUR)
+            .credit("1200", Dec!(-10) * EUR);
+        assert_eq!(
+            entry.check_balance(),
+            Err(MoneyError::NegativeAmount {
+                account: "6310".to_string(),
+                amount: Dec!(-10) * EUR
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_ledger {
+    use moneta::{
+        Account, Date, Dec, Decimal, JournalEntry, Ledger, MoneyError, EUR,
+        USD,
+    };
+
+    fn ledger() -> Ledger {
+        let mut ledger = Ledger::new();
+        for account in [
+            Account::new("1200", "Bank", EUR),
+            Account::multi_currency("1790", "Clearing"),
+            Account::new("8400", "Revenue", EUR),
+            Account::new("1210", "Bank USD", USD),
+        ] {
+            ledger.open_account(account).unwrap();
+        }
+        ledger
+    }
+
+    #[test]
+    fn test_accounts() {
+        let mut ledger = ledger();
+        assert_eq!(ledger.account("1200").unwrap().name(), "Bank");
+        assert_eq!(ledger.account("1200").unwrap().currency(), Some(EUR));
+        assert_eq!(ledger.account("1790").unwrap().currency(), None);
+        assert!(ledger.account("9999").is_none());
+        let codes: Vec<&str> = ledger.accounts().map(Account::code).collect();
+        assert_eq!(codes, ["1200", "1210", "1790", "8400"]);
+        assert_eq!(
+            ledger.open_account(Account::new("1200", "Cash", EUR)),
+            Err(MoneyError::DuplicateAccount {
+                code: "1200".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_post() {
+        let mut ledger = ledger();
+        let date = Date::new(2024, 3, 1);
+        assert_eq!(
+            ledger.post(
+                JournalEntry::new(date, "Unknown")
+                    .debit("1000", Dec!(10) * EUR)
+                    .credit("8400", Dec!(10) * EUR)
+            ),
+            Err(MoneyError::UnknownAccount {
+                code: "1000".to_string()
+            })
+        );
+        assert_eq!(
+            ledger.post(
+                JournalEntry::new(date, "Wrong currency")
+                    .debit("1210", Dec!(10) * EUR)
+                    .credit("8400", Dec!(10) * EUR)
+            ),
+            Err(MoneyError::CurrencyMismatch {
+                expected: USD,
+                found: EUR
+            })
+        );
+        assert!(ledger
+            .post(
+                JournalEntry::new(date, "Unbalanced")
+                    .debit("1200", Dec!(10) * EUR)
+                    .credit("8400", Dec!(9) * EUR)
+            )
+            .is_err());
+        assert!(ledger.entries().is_empty());
+        ledger
+            .post(
+                JournalEntry::new(date, "Invoice")
+                    .debit("1200", Dec!(10) * EUR)
+                    .credit("8400", Dec!(10) * EUR),
+            )
+            .unwrap();
+        assert_eq!(ledger.entries().len(), 1);
+    }
+
+    #[test]
+    fn test_balances() {
+        let mut ledger = ledger();
+        ledger
+            .post(
+                JournalEntry::new(Date::new(2024, 3, 1), "Invoice 1")
+                    .debit("1200", Dec!(250) * EUR)
+                    .credit("8400", Dec!(250) * EUR),
+            )
+            .unwrap();
+        ledger
+            .post(
+                JournalEntry::new(Date::new(2024, 3, 15), "FX")
+                    .debit("1790", Dec!(100) * EUR)
+                    .credit("1200", Dec!(100) * EUR)
+                    .debit("1210", Dec!(108.5) * USD)
+                    .credit("1790", Dec!(108.5) * USD),
+            )
+            .unwrap();
+        ledger
+            .post(
+                JournalEntry::new(Date::new(2024, 4, 2), "Invoice 2")
+                    .debit("1200", Dec!(80) * EUR)
+                    .credit("8400", Dec!(80) * EUR),
+            )
+            .unwrap();
+        let march = Date::new(2024, 3, 31);
+        assert_eq!(ledger.balance("1200", EUR, march), Ok(Dec!(150) * EUR));
+        assert_eq!(
+            ledger.balance("1200", EUR, Date::new(2024, 3, 1)),
+            Ok(Dec!(250) * EUR)
+        );
+        assert_eq!(
+            ledger.balance("1200", EUR, Date::new(2024, 2, 29)),
+            Ok(Dec!(0) * EUR)
+        );
+        assert_eq!(
+            ledger.balance("1200", EUR, Date::new(2024, 4, 30)),
+            Ok(Dec!(230) * EUR)
+        );
+        assert_eq!(ledger.balance("8400", EUR, march), Ok(Dec!(-250) * EUR));
+        assert_eq!(
+            ledger.balances("1790", march),
+            Ok(vec![Dec!(100) * EUR, Dec!(-108.5) * USD])
+        );
+        assert_eq!(
+            ledger.balances("1790", Date::new(2024, 1, 1)),
+            Ok(vec![])
+        );
+        assert!(ledger.balance("9999", EUR, march).is_err());
+        assert!(ledger.balances("9999", march).is_err());
+    }
+
+    #[test]
+    fn test_trial_balance() {
+        let mut ledger = ledger();
+        ledger
+            .post(
+                JournalEntry::new(Date::new(2024, 3, 1), "Invoice 1")
+                    .debit("1200", Dec!(250) * EUR)
+                    .credit("8400", Dec!(250) * EUR),
+            )
+            .unwrap();
+        ledger
+            .post(
+                JournalEntry::new(Date::new(2024, 3, 15), "FX")
+                    .debit("1790", Dec!(100) * EUR)
+                    .credit("1200", Dec!(100) * EUR)
+                    .debit("1210", Dec!(108.5) * USD)
+                    .credit("1790", Dec!(108.5) * USD),
+            )
+            .unwrap();
+        let as_of = Date::new(2024, 3, 31);
+        let tb = ledger.trial_balance(as_of);
+        assert_eq!(tb.as_of(), as_of);
+        let lines: Vec<(&str, _, _)> = tb
+            .lines()
+            .iter()
+            .map(|line| (line.account(), line.debit(), line.credit()))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                ("1200", Dec!(150) * EUR, Dec!(0) * EUR),
+                ("1210", Dec!(108.5) * USD, Dec!(0) * USD),
+                ("1790", Dec!(100) * EUR, Dec!(0) * EUR),
+                ("1790", Dec!(0) * USD, Dec!(108.5) * USD),
+                ("8400", Dec!(0) * EUR, Dec!(250) * EUR),
+            ]
+        );
+        assert_eq!(tb.currencies(), [EUR, USD]);
+        assert_eq!(tb.total_debit(EUR), Dec!(250) * EUR);
+        assert_eq!(tb.total_credit(EUR), Dec!(250) * EUR);
+        assert_eq!(tb.total_debit(USD), Dec!(108.5) * USD);
+        assert!(tb.is_balanced());
+        assert!(ledger
+            .trial_balance(Date::new(2024, 1, 1))
+            .lines()
+            .is_empty());
+    }
+}
@@ -50,11 +50,80 @@ mod test_money_formatting {
             "UYW    +834.2870"
         );
     }
+
+    #[test]
+    fn test_money_fmt_padding() {
+        let val = Dec!(-2091.5) * USD;
+        assert_eq!(format!("{:^17}", val), "  -2091.50 USD   ");
+        assert_eq!(format!("{:*<16.1}", val), "-2091.5 USD*****");
+        assert_eq!(format!("{:016}", val), "-00002091.50 USD");
+        assert_eq!(
+            format!("{:+017.0}", Dec!(2091.5) * USD),
+            "+000000002092 USD"
+        );
+        assert_eq!(format!("{:5}", val), "-2091.50 USD");
+    }
+
+    #[test]
+    fn test_zero_money_fmt() {
+        let val = Dec!(0) * USD;
+        assert_eq!(val.to_string(), "0.00 USD");
+        assert_eq!(format!("{:+}", val), "+0.00 USD");
+    }
+
+    #[test]
+    fn test_currency_code_and_name() {
+        assert_eq!(USD.code(), "USD");
+        assert_eq!(USD.name_str(), "US Dollar");
+        assert_eq!(UYW.code(), "UYW");
+        assert_eq!(UYW.name_str(), "Unidad Previsional");
+    }
 }
 
 #[cfg(test)]
 mod test_money_ops {
-    use moneta::{Dec, Decimal, Quantity, USD, UYW};
+    use moneta::{Dec, Decimal, Money, MoneyError, Quantity, USD, UYW};
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_operands() {
+        let x = Dec!(27.4) * USD;
+        let y = Dec!(35.89) * USD;
+        assert_eq!(&x + &y, x + y);
+        assert_eq!(&x + y, x + y);
+        assert_eq!(x + &y, x + y);
+        assert_eq!(&x - &y, x - y);
+        assert_eq!(&x / &y, x / y);
+        assert_eq!(&x * Dec!(1.5), x * Dec!(1.5));
+        assert_eq!(Dec!(1.5) * &x, x * Dec!(1.5));
+        assert_eq!(&x / Dec!(3), x / Dec!(3));
+    }
+
+    #[test]
+    fn test_op_assign() {
+        let y = Dec!(35.89) * USD;
+        let mut x = Dec!(27.4) * USD;
+        x += y;
+        assert_eq!(x, Dec!(63.29) * USD);
+        x -= &y;
+        assert_eq!(x, Dec!(27.4) * USD);
+        x *= Dec!(1.005);
+        assert_eq!(x, Dec!(27.54) * USD);
+        x /= Dec!(7);
+        assert_eq!(x, Dec!(3.93) * USD);
+        let mut total = Money::zero(USD);
+        for m in [x, y] {
+            total += m;
+        }
+        assert_eq!(total, Dec!(39.82) * USD);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_assign_diff_currency() {
+        let mut x = Dec!(27.4) * USD;
+        x += Dec!(35.89) * UYW;
+    }
 
     #[test]
     fn test_add_sub_same_currency() {
@@ -85,6 +154,21 @@ mod test_money_ops {
         let _z = x - y;
     }
 
+    #[test]
+    fn test_try_add_sub() {
+        let x = Dec!(27.4) * USD;
+        let y = Dec!(35.89) * USD;
+        assert_eq!(x.try_add(y), Ok(x + y));
+        assert_eq!(x.try_sub(y), Ok(x - y));
+        let y = Dec!(35.89) * UYW;
+        let err = MoneyError::CurrencyMismatch {
+            expected: USD,
+            found: UYW,
+        };
+        assert_eq!(x.try_add(y), Err(err.clone()));
+        assert_eq!(x.try_sub(y), Err(err));
+    }
+
     #[test]
     fn test_mul_amnt_money() {
         let x = Dec!(7.5);
@@ -113,6 +197,158 @@ mod test_money_ops {
     }
 }
 
+#[cfg(test)]
+mod test_money_sign {
+    use moneta::{Dec, Decimal, Money, Quantity, USD};
+
+    #[test]
+    fn test_zero() {
+        let z = Money::zero(USD);
+        assert_eq!(z.amount(), Dec!(0));
+        assert_eq!(z.unit(), USD);
+        assert!(z.is_zero());
+        assert!(!z.is_negative());
+        assert!(!z.is_positive());
+        assert_eq!(z.signum(), Dec!(0));
+        assert_eq!(-z, z);
+        assert_eq!((-z).to_string(), "0.00 USD");
+    }
+
+    #[test]
+    fn test_neg_abs_signum() {
+        let x = Dec!(27.4) * USD;
+        assert_eq!(-x, Dec!(-27.4) * USD);
+        assert_eq!(-&x, Dec!(-27.4) * USD);
+        assert_eq!(-(-x), x);
+        assert!(x.is_positive());
+        assert!((-x).is_negative());
+        assert!(!x.is_zero());
+        assert_eq!(x.abs(), x);
+        assert_eq!((-x).abs(), x);
+        assert_eq!(x.signum(), Dec!(1));
+        assert_eq!((-x).signum(), Dec!(-1));
+    }
+}
+
+#[cfg(test)]
+mod test_money_parsing {
+    use moneta::{
+        CurrencyKeyError, Dec, Decimal, Money, MoneyError, ParseDecimalError,
+        USD, UYW,
+    };
+
+    #[test]
+    fn test_parse_money() {
+        assert_eq!("-18.95 USD".parse::<Money>(), Ok(Dec!(-18.95) * USD));
+        assert_eq!(" 18.9  UYW ".parse::<Money>(), Ok(Dec!(18.9) * UYW));
+        assert_eq!("18.955 USD".parse::<Money>(), Ok(Dec!(18.96) * USD));
+        let m = Dec!(2091.5) * UYW;
+        assert_eq!(m.to_string().parse::<Money>(), Ok(m));
+    }
+
+    #[test]
+    fn test_parse_money_errors() {
+        assert_eq!(
+            "18.95USD".parse::<Money>(),
+            Err(MoneyError::InvalidFormat {
+                input: "18.95USD".into()
+            })
+        );
+        assert_eq!(
+            "18,95 USD".parse::<Money>(),
+            Err(MoneyError::InvalidAmount(ParseDecimalError::Invalid))
+        );
+        assert_eq!(
+            "18.95 XYZ".parse::<Money>(),
+            Err(MoneyError::UnknownCurrency {
+                symbol: "XYZ".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = "18.95 XYZ".parse::<Money>().unwrap_err();
+        assert_eq!(err.to_string(), "Unknown currency 'XYZ'.");
+        let err = MoneyError::from(CurrencyKeyError::AlreadyRegistered {
+            symbol: "XYZ".into(),
+        });
+        assert_eq!(
+            err.to_string(),
+            "A currency with symbol 'XYZ' is already registered."
+        );
+        let err: Box<dyn std::error::Error> = Box::new(err);
+        assert!(err.source().is_some());
+    }
+}
+
+#[cfg(test)]
+mod test_money_aggregation {
+    use moneta::{Dec, Decimal, Money, MoneyError, EUR, USD};
+
+    #[test]
+    fn test_sum() {
+        let payments = vec![Dec!(17.5) * EUR, Dec!(4.25) * EUR];
+        let total: Option<Money> = payments.iter().sum();
+        assert_eq!(total, Some(Dec!(21.75) * EUR));
+        let total: Result<Money, MoneyError> = payments.into_iter().sum();
+        assert_eq!(total, Ok(Dec!(21.75) * EUR));
+    }
+
+    #[test]
+    fn test_sum_empty_or_mixed() {
+        let payments: [Money; 0] = [];
+        let total: Option<Money> = payments.iter().sum();
+        assert_eq!(total, None);
+        let total: Result<Money, MoneyError> = payments.iter().sum();
+        assert_eq!(total, Err(MoneyError::Empty));
+        let payments = [Dec!(17.5) * EUR, Dec!(4.25) * USD];
+        let total: Option<Money> = payments.iter().sum();
+        assert_eq!(total, None);
+        assert_eq!(
+            Money::try_sum(payments),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: USD
+            })
+        );
+    }
+
+    #[test]
+    fn test_sum_in() {
+        let payments = [Dec!(17.5) * EUR, Dec!(-4.25) * EUR];
+        assert_eq!(Money::sum_in(EUR, payments), Ok(Dec!(13.25) * EUR));
+        assert_eq!(Money::sum_in(USD, [] as [Money; 0]), Ok(Dec!(0) * USD));
+        assert!(Money::sum_in(USD, payments).is_err());
+    }
+
+    #[test]
+    fn test_min_max_in() {
+        let payments = [Dec!(4.25) * EUR, Dec!(17.5) * EUR, Dec!(-3) * EUR];
+        assert_eq!(Money::min_in(EUR, payments), Ok(Some(Dec!(-3) * EUR)));
+        assert_eq!(Money::max_in(EUR, payments), Ok(Some(Dec!(17.5) * EUR)));
+        assert_eq!(Money::min_in(EUR, [] as [Money; 0]), Ok(None));
+        assert_eq!(Money::max_in(USD, [] as [Money; 0]), Ok(None));
+        assert!(Money::max_in(USD, payments).is_err());
+    }
+
+    #[test]
+    fn test_average_in() {
+        let payments = [Dec!(10) * EUR, Dec!(10) * EUR, Dec!(10.02) * EUR];
+        assert_eq!(
+            Money::average_in(EUR, payments),
+            Ok(Some(Dec!(10.01) * EUR))
+        );
+        let payments = [Dec!(1) * USD, Dec!(1) * USD, Dec!(0.01) * USD];
+        assert_eq!(
+            Money::average_in(USD, payments),
+            Ok(Some(Dec!(0.67) * USD))
+        );
+        assert_eq!(Money::average_in(EUR, [] as [Money; 0]), Ok(None));
+        assert!(Money::average_in(EUR, payments).is_err());
+    }
+}
+
 #[cfg(test)]
 mod test_money_rated_to_qty {
     use moneta::{Dec, Decimal, Money, Quantity, USD, UYW};
@@ -162,3 +398,66 @@ mod test_money_rated_to_qty {
         let _mr = d / r;
     }
 }
+
+#[cfg(test)]
+mod test_money_ordering {
+    use std::collections::{BTreeMap, HashSet};
+
+    use moneta::{
+        Dec, Decimal, Money, OrderedMoney, Quantity, EUR, JPY, USD,
+    };
+
+    #[test]
+    fn test_total_cmp() {
+        let mut v = vec![
+            Dec!(5) * USD,
+            Dec!(700) * JPY,
+            Dec!(-3) * USD,
+            Dec!(7) * EUR,
+            Dec!(0) * EUR,
+        ];
+        v.sort_by(Money::total_cmp);
+        assert_eq!(
+            v,
+            [
+                Dec!(0) * EUR,
+                Dec!(7) * EUR,
+                Dec!(700) * JPY,
+                Dec!(-3) * USD,
+                Dec!(5) * USD,
+            ]
+        );
+        let m = Dec!(7) * EUR;
+        assert!(m.total_cmp(&Money::new(Dec!(7.000), EUR)).is_eq());
+    }
+
+    #[test]
+    fn test_ordered_money() {
+        let mut totals: BTreeMap<OrderedMoney, usize> = BTreeMap::new();
+        for m in [Dec!(5) * USD, Dec!(7) * EUR, Dec!(5) * USD] {
+            *totals.entry(m.into()).or_default() += 1;
+        }
+        let entries: Vec<(Money, usize)> =
+            totals.into_iter().map(|(k, v)| (k.into(), v)).collect();
+        assert_eq!(entries, [(Dec!(7) * EUR, 1), (Dec!(5) * USD, 2)]);
+        let m = OrderedMoney::new(Dec!(3.5) * EUR);
+        assert_eq!(m.money(), Dec!(3.5) * EUR);
+        assert_eq!(m.to_string(), "3.50 EUR");
+        assert!(m < OrderedMoney::new(Dec!(3.5) * USD));
+    }
+
+    #[test]
+    fn test_hash() {
+        let set: HashSet<Money> = [
+            Dec!(5) * USD,
+            Dec!(5.00) * USD,
+            Money::new(Dec!(4.999), USD),
+            Dec!(5) * EUR,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&(Dec!(5) * EUR)));
+        assert!(!set.contains(&(Dec!(5) * JPY)));
+    }
+}
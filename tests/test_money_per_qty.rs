@@ -0,0 +1,98 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_money_per_qty {
+    use moneta::{
+        Dec, Decimal, ExchangeRate, Money, MoneyError, MoneyPerQty, Price,
+        Quantity, EUR, GBP, JPY, USD,
+    };
+    use quantities::prelude::*;
+
+    #[quantity]
+    #[ref_unit(Kilogram, "kg", KILO, "Reference unit of quantity `Mass`")]
+    #[unit(Gram, "g", NONE, 0.001, "0.001·kg")]
+    #[unit(Pound, "lb", 0.45359237, "0.45359237·kg")]
+    struct Mass {}
+
+    #[test]
+    fn test_constructors() {
+        let r = (Dec!(3.5) * EUR).per_unit(KILOGRAM);
+        assert_eq!(r.term_amount(), Dec!(3.5));
+        assert_eq!(r.term_unit(), EUR);
+        assert_eq!(r.per_unit_multiple(), Dec!(1));
+        assert_eq!(r.per_unit(), KILOGRAM);
+        let r = (Dec!(1.25) * EUR).per(Dec!(100) * GRAM);
+        assert_eq!(r.per_unit_multiple(), Dec!(100));
+        assert_eq!(r.per_unit(), GRAM);
+        assert_eq!(r * (Dec!(350) * GRAM), Dec!(4.38) * EUR);
+        assert_eq!(Dec!(4.38) * EUR / r, Dec!(350.4) * GRAM);
+    }
+
+    #[test]
+    fn test_convert_currency() {
+        let r = (Dec!(3.5) * EUR).per_unit(KILOGRAM);
+        let eur_usd = ExchangeRate::new(EUR, 1, USD, Dec!(1.08));
+        let c = r.convert_currency(eur_usd).unwrap();
+        assert_eq!(c.term_amount(), Dec!(3.78));
+        assert_eq!(c.term_unit(), USD);
+        assert_eq!(c.per_unit(), KILOGRAM);
+        let c = c.convert_currency(eur_usd).unwrap();
+        assert_eq!(c.term_unit(), EUR);
+        assert_eq!(c.term_amount(), Dec!(3.5));
+        assert_eq!(
+            r.convert_currency(ExchangeRate::new(USD, 1, GBP, Dec!(0.79)))
+                .err(),
+            Some(MoneyError::CurrencyMismatch {
+                expected: USD,
+                found: EUR
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_per_unit() {
+        let r = Price::new(Dec!(12.499), EUR, 3).per(Dec!(1), KILOGRAM);
+        let c = r.convert_per_unit(GRAM);
+        assert_eq!(c.term_amount(), Dec!(0.012499));
+        assert_eq!(c.per_unit(), GRAM);
+        assert_eq!(c * (Dec!(250) * GRAM), r * (Dec!(250) * GRAM));
+        let c = r.convert_per_unit(POUND);
+        assert_eq!(c.term_amount(), Dec!(5.66945103263));
+        assert_eq!(
+            Price::from_rate(&c.convert_per_unit(KILOGRAM), 3),
+            Ok(Price::new(Dec!(12.499), EUR, 3))
+        );
+        // the per unit multiple is kept
+        let r = (Dec!(1.25) * EUR).per(Dec!(100) * GRAM);
+        let c = r.convert_per_unit(KILOGRAM);
+        assert_eq!(c.term_amount(), Dec!(1250));
+        assert_eq!(c.per_unit_multiple(), Dec!(100));
+    }
+
+    #[test]
+    fn test_display() {
+        let r = (Dec!(3.5) * EUR).per_unit(KILOGRAM);
+        assert_eq!(r.display().to_string(), "3.50 EUR/kg");
+        assert_eq!(format!("{:.3}", r.display()), "3.500 EUR/kg");
+        assert_eq!(format!("{:>14}", r.display()), "   3.50 EUR/kg");
+        assert_eq!(format!("{:*<14.1}", r.display()), "3.5 EUR/kg****");
+        let r = (Dec!(-3.5) * EUR).per_unit(KILOGRAM);
+        assert_eq!(format!("{:^14}", r.display()), " -3.50 EUR/kg ");
+        let r = (Dec!(1.25) * EUR).per(Dec!(100) * GRAM);
+        assert_eq!(r.display().to_string(), "1.25 EUR/100 g");
+        let r: Rate<Money, Mass> =
+            Rate::new(Dec!(349.5), JPY, Dec!(1), POUND);
+        assert_eq!(r.display().to_string(), "350 JPY/lb");
+        assert_eq!(
+            format!("{:?}", r.display()),
+            "MoneyPerQtyDisplay(\"350 JPY/lb\")"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_percent {
+    use moneta::{BasisPoints, Dec, Decimal, Percent};
+
+    #[test]
+    fn test_percent_fraction() {
+        let p = Percent::new(Dec!(19));
+        assert_eq!(p.value(), Dec!(19));
+        assert_eq!(p.as_fraction(), Dec!(0.19));
+        assert_eq!(Percent::from_fraction(Dec!(0.19)), p);
+        assert_eq!(Percent::HUNDRED.as_fraction(), Dec!(1));
+    }
+
+    #[test]
+    fn test_basis_points_fraction() {
+        let bp = BasisPoints::new(Dec!(25));
+        assert_eq!(bp.value(), Dec!(25));
+        assert_eq!(bp.as_fraction(), Dec!(0.0025));
+        assert_eq!(BasisPoints::from_fraction(Dec!(0.0025)), bp);
+    }
+
+    #[test]
+    fn test_conversion() {
+        let bp = BasisPoints::new(Dec!(1900));
+        let p = Percent::new(Dec!(19));
+        assert_eq!(Percent::from(bp), p);
+        assert_eq!(BasisPoints::from(p), bp);
+        assert!(Percent::new(Dec!(0.5)) < Percent::new(Dec!(1)));
+    }
+
+    #[test]
+    fn test_fmt() {
+        let p = Percent::new(Dec!(7.5));
+        assert_eq!(p.to_string(), "7.5 %");
+        assert_eq!(format!("{p:.2}"), "7.50 %");
+        assert_eq!(format!("{p:>8}"), "   7.5 %");
+        assert_eq!(format!("{p:*<8.1}"), "7.5 %***");
+        assert_eq!(format!("{p:^+9}"), " +7.5 %  ");
+        let bp = BasisPoints::new(Dec!(-12));
+        assert_eq!(bp.to_string(), "-12 bp");
+        assert_eq!(format!("{bp:>9.1}"), " -12.0 bp");
+        assert_eq!(format!("{bp:08}"), "-0012 bp");
+    }
+}
+
+#[cfg(test)]
+mod test_money_percent {
+    use moneta::{
+        BasisPoints, Dec, Decimal, MoneyError, Percent, EUR, JPY, USD,
+    };
+
+    #[test]
+    fn test_mul_percent() {
+        let m = Dec!(24.99) * EUR;
+        let p = Percent::new(Dec!(19));
+        assert_eq!(m * p, Dec!(4.75) * EUR);
+        assert_eq!(p * m, Dec!(4.75) * EUR);
+        assert_eq!(m.percent_of(p), Dec!(4.75) * EUR);
+        assert_eq!(m * Percent::HUNDRED, m);
+        let m = Dec!(1999) * JPY;
+        assert_eq!(m * Percent::new(Dec!(8)), Dec!(160) * JPY);
+    }
+
+    #[test]
+    fn test_mul_basis_points() {
+        let m = Dec!(12345.67) * USD;
+        let bp = BasisPoints::new(Dec!(15));
+        assert_eq!(m * bp, Dec!(18.52) * USD);
+        assert_eq!(bp * m, Dec!(18.52) * USD);
+        assert_eq!(m * bp, m * Percent::from(bp));
+    }
+
+    #[test]
+    fn test_ratio_to() {
+        let part = Dec!(19) * EUR;
+        let total = Dec!(100) * EUR;
+        assert_eq!(part.ratio_to(&total), Ok(Percent::new(Dec!(19))));
+        let part = Dec!(1) * EUR;
+        let total = Dec!(3) * EUR;
+        let p = part.ratio_to(&total).unwrap();
+        assert_eq!(p.value(), Dec!(33.333333333333333333));
+        assert_eq!(total * p, part);
+    }
+
+    #[test]
+    fn test_ratio_to_diff_currency() {
+        assert_eq!(
+            (Dec!(1) * EUR).ratio_to(&(Dec!(3) * USD)),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: USD
+            })
+        );
+    }
+
+    #[test]
+    fn test_ratio_to_zero() {
+        assert_eq!(
+            (Dec!(1) * EUR).ratio_to(&(Dec!(0) * EUR)),
+            Err(MoneyError::DivisionByZero)
+        );
+    }
+}
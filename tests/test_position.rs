@@ -0,0 +1,187 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_currency_position {
+    use moneta::{
+        CostMethod, CurrencyPosition, Dec, Decimal, ExchangeRate, MoneyError,
+        EUR, GBP, JPY, USD,
+    };
+
+    fn usd_eur(rate: Decimal) -> ExchangeRate {
+        ExchangeRate::new(USD, 1, EUR, rate)
+    }
+
+    fn position(method: CostMethod) -> CurrencyPosition {
+        let mut pos = CurrencyPosition::new(USD, EUR, method);
+        pos.buy(Dec!(1000) * USD, usd_eur(Dec!(0.90))).unwrap();
+        pos.buy(Dec!(1000) * USD, usd_eur(Dec!(0.95))).unwrap();
+        pos
+    }
+
+    #[test]
+    fn test_new() {
+        let pos = CurrencyPosition::new(USD, EUR, CostMethod::default());
+        assert_eq!(pos.currency(), USD);
+        assert_eq!(pos.base_currency(), EUR);
+        assert_eq!(pos.method(), CostMethod::Fifo);
+        assert!(pos.quantity().is_zero());
+        assert!(pos.cost_basis().is_zero());
+        assert!(pos.realized_pnl().is_zero());
+        assert_eq!(pos.average_cost(), Ok(None));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_same_currencies() {
+        let _p = CurrencyPosition::new(USD, USD, CostMethod::Fifo);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let pos = CurrencyPosition::try_new(USD, EUR, CostMethod::Lifo);
+        assert_eq!(pos.unwrap().method(), CostMethod::Lifo);
+        assert_eq!(
+            CurrencyPosition::try_new(USD, USD, CostMethod::Fifo),
+            Err(MoneyError::IdenticalRateCurrencies(USD))
+        );
+    }
+
+    #[test]
+    fn test_buy() {
+        let pos = position(CostMethod::Fifo);
+        assert_eq!(pos.quantity(), Dec!(2000) * USD);
+        assert_eq!(pos.cost_basis(), Dec!(1850) * EUR);
+        assert_eq!(pos.average_cost(), Ok(Some(usd_eur(Dec!(0.925)))));
+        assert_eq!(
+            pos.unrealized_pnl(usd_eur(Dec!(0.96))),
+            Ok(Dec!(70) * EUR)
+        );
+    }
+
+    #[test]
+    fn test_fifo() {
+        let mut pos = position(CostMethod::Fifo);
+        let pnl = pos.sell(Dec!(1500) * USD, usd_eur(Dec!(0.96))).unwrap();
+        assert_eq!(pnl, Dec!(65) * EUR);
+        assert_eq!(pos.quantity(), Dec!(500) * USD);
+        assert_eq!(pos.cost_basis(), Dec!(475) * EUR);
+        assert_eq!(pos.realized_pnl(), Dec!(65) * EUR);
+        assert_eq!(
+            pos.unrealized_pnl(usd_eur(Dec!(0.96))),
+            Ok(Dec!(5) * EUR)
+        );
+    }
+
+    #[test]
+    fn test_lifo() {
+        let mut pos = position(CostMethod::Lifo);
+        let pnl = pos.sell(Dec!(1500) * USD, usd_eur(Dec!(0.96))).unwrap();
+        assert_eq!(pnl, Dec!(40) * EUR);
+        assert_eq!(pos.cost_basis(), Dec!(450) * EUR);
+        let pnl = pos.sell(Dec!(500) * USD, usd_eur(Dec!(0.85))).unwrap();
+        assert_eq!(pnl, Dec!(-25) * EUR);
+        assert_eq!(pos.realized_pnl(), Dec!(15) * EUR);
+        assert!(pos.quantity().is_zero());
+        assert!(pos.cost_basis().is_zero());
+    }
+
+    #[test]
+    fn test_weighted_average() {
+        let mut pos = position(CostMethod::WeightedAverage);
+        let pnl = pos.sell(Dec!(1500) * USD, usd_eur(Dec!(0.96))).unwrap();
+        assert_eq!(pnl, Dec!(52.50) * EUR);
+        assert_eq!(pos.cost_basis(), Dec!(462.50) * EUR);
+        assert_eq!(pos.average_cost(), Ok(Some(usd_eur(Dec!(0.925)))));
+        pos.buy(Dec!(500) * USD, usd_eur(Dec!(0.975))).unwrap();
+        assert_eq!(pos.cost_basis(), Dec!(950) * EUR);
+        assert_eq!(pos.average_cost(), Ok(Some(usd_eur(Dec!(0.95)))));
+    }
+
+    #[test]
+    fn test_rounding() {
+        let mut pos =
+            CurrencyPosition::new(USD, JPY, CostMethod::WeightedAverage);
+        let rate = ExchangeRate::new(USD, 1, JPY, Dec!(151.237));
+        pos.buy(Dec!(100) * USD, rate).unwrap();
+        assert_eq!(pos.cost_basis(), Dec!(15124) * JPY);
+        pos.buy(Dec!(100) * USD, ExchangeRate::new(USD, 1, JPY, Dec!(149.5)))
+            .unwrap();
+        let rate = ExchangeRate::new(USD, 1, JPY, Dec!(150));
+        let pnl = pos.sell(Dec!(66.67) * USD, rate).unwrap();
+        // proceeds 10000 (10000.5 rounded half to even), cost 10025
+        assert_eq!(pnl, Dec!(-25) * JPY);
+        assert_eq!(pos.cost_basis(), Dec!(20049) * JPY);
+        let pnl = pos.sell(Dec!(133.33) * USD, rate).unwrap();
+        // proceeds 20000 (19999.5 rounded half to even), cost 20049
+        assert_eq!(pnl, Dec!(-49) * JPY);
+        assert!(pos.cost_basis().is_zero());
+        assert_eq!(pos.realized_pnl(), Dec!(-74) * JPY);
+    }
+
+    #[test]
+    fn test_inverse_rate() {
+        let mut pos = CurrencyPosition::new(USD, EUR, CostMethod::Fifo);
+        pos.buy(Dec!(1100) * USD, ExchangeRate::new(EUR, 1, USD, Dec!(1.1)))
+            .unwrap();
+        assert_eq!(pos.cost_basis(), Dec!(1000) * EUR);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut pos = position(CostMethod::Fifo);
+        assert_eq!(
+            pos.sell(Dec!(2000.01) * USD, usd_eur(Dec!(0.96))),
+            Err(MoneyError::InsufficientQuantity {
+                available: Dec!(2000) * USD,
+                requested: Dec!(2000.01) * USD
+            })
+        );
+        assert_eq!(
+            pos.buy(Dec!(10) * EUR, usd_eur(Dec!(0.96))),
+            Err(MoneyError::CurrencyMismatch {
+                expected: USD,
+                found: EUR
+            })
+        );
+        assert_eq!(
+            pos.buy(
+                Dec!(10) * USD,
+                ExchangeRate::new(USD, 1, GBP, Dec!(0.79))
+            ),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: GBP
+            })
+        );
+        assert_eq!(pos, position(CostMethod::Fifo));
+        assert_eq!(
+            MoneyError::InsufficientQuantity {
+                available: Dec!(20) * USD,
+                requested: Dec!(30) * USD
+            }
+            .to_string(),
+            "Requested 30.00 USD, but only 20.00 USD available."
+        );
+    }
+
+    #[test]
+    fn test_not_positive() {
+        let mut pos = position(CostMethod::Fifo);
+        assert_eq!(
+            pos.sell(Dec!(0) * USD, usd_eur(Dec!(0.96))),
+            Err(MoneyError::NotPositive(Dec!(0) * USD))
+        );
+        assert_eq!(
+            pos.buy(Dec!(-10) * USD, usd_eur(Dec!(0.96))),
+            Err(MoneyError::NotPositive(Dec!(-10) * USD))
+        );
+        assert_eq!(pos.quantity(), Dec!(2000) * USD);
+    }
+}
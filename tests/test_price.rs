@@ -0,0 +1,129 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_price {
+    use std::collections::HashSet;
+
+    use moneta::{Dec, Decimal, MoneyError, Price, EUR, JPY, USD};
+
+    #[test]
+    fn test_new() {
+        let p = Price::new(Dec!(1.79949), EUR, 3);
+        assert_eq!(p.amount(), Dec!(1.799));
+        assert_eq!(p.currency(), EUR);
+        assert_eq!(p.precision(), 3);
+        assert_eq!(p.to_money(), Dec!(1.80) * EUR);
+        let p = Price::new(Dec!(0.0000125), USD, 6);
+        assert_eq!(p.amount(), Dec!(0.000012));
+        assert!(p.to_money().is_zero());
+        let p = Price::new(Dec!(151.2375), JPY, 0);
+        assert_eq!(p.amount(), Dec!(151));
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            Price::try_new(Dec!(1.799), EUR, 1),
+            Err(MoneyError::InvalidPrecision {
+                currency: EUR,
+                precision: 1
+            })
+        );
+        assert!(Price::try_new(Dec!(1.799), EUR, 19).is_err());
+        assert!(Price::try_new(Dec!(1.799), EUR, 18).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_invalid_precision() {
+        let _p = Price::new(Dec!(1.799), EUR, 0);
+    }
+
+    #[test]
+    fn test_from_money() {
+        let p = Price::from(Dec!(3.5) * EUR);
+        assert_eq!(p.precision(), 2);
+        assert_eq!(p.to_string(), "3.50 EUR");
+        assert_eq!(p, Price::new(Dec!(3.5), EUR, 4));
+    }
+
+    #[test]
+    fn test_mul() {
+        let p = Price::new(Dec!(0.000012), USD, 6);
+        assert_eq!(p * Dec!(1000), Dec!(0.01) * USD);
+        assert_eq!(p * Dec!(999), Dec!(0.01) * USD);
+        assert_eq!(Dec!(416) * p, Dec!(0) * USD);
+        assert_eq!(Dec!(417) * p, Dec!(0.01) * USD);
+        let p = Price::new(Dec!(1.799), EUR, 3);
+        assert_eq!(p * Dec!(-10), Dec!(-17.99) * EUR);
+    }
+
+    #[test]
+    fn test_cmp_and_hash() {
+        let x = Price::new(Dec!(1.799), EUR, 3);
+        let y = Price::new(Dec!(1.8), EUR, 4);
+        assert!(x < y);
+        assert_eq!(y, Price::new(Dec!(1.8), EUR, 2));
+        assert_ne!(y, Price::new(Dec!(1.8), USD, 2));
+        assert_eq!(x.partial_cmp(&Price::new(Dec!(1.8), USD, 2)), None);
+        let set: HashSet<Price> =
+            [x, y, Price::new(Dec!(1.80), EUR, 2)].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_display() {
+        let p = Price::new(Dec!(1.79), EUR, 3);
+        assert_eq!(p.to_string(), "1.790 EUR");
+        assert_eq!(format!("{p:.4}"), "1.7900 EUR");
+        assert_eq!(format!("{p:>11}"), "  1.790 EUR");
+        assert_eq!(format!("{p:*<11.2}"), "1.79 EUR***");
+        assert_eq!(format!("{p:^+12}"), " +1.790 EUR ");
+        let p = Price::new(Dec!(-0.000012), USD, 6);
+        assert_eq!(p.to_string(), "-0.000012 USD");
+        assert_eq!(format!("{p:015}"), "-000.000012 USD");
+    }
+}
+
+#[cfg(test)]
+mod test_price_per_qty {
+    use moneta::{Dec, Decimal, Money, MoneyError, Price, Quantity, EUR};
+    use quantities::prelude::*;
+
+    #[quantity]
+    #[ref_unit(Kilogram, "kg", KILO, "Reference unit of quantity `Mass`")]
+    #[unit(Gram, "g", NONE, 0.001, "0.001·kg")]
+    struct Mass {}
+
+    #[test]
+    fn test_per() {
+        let p = Price::new(Dec!(12.499), EUR, 3);
+        let r: Rate<Money, Mass> = p.per(Dec!(1), KILOGRAM);
+        assert_eq!(r.term_amount(), Dec!(12.499));
+        assert_eq!(r * (Dec!(2) * KILOGRAM), Dec!(25) * EUR);
+        assert_eq!(r * (Dec!(250) * GRAM), Dec!(3.12) * EUR);
+        let r: Rate<Money, Mass> = p.per(Dec!(100), GRAM);
+        assert_eq!(r * (Dec!(250) * GRAM), Dec!(31.25) * EUR);
+    }
+
+    #[test]
+    fn test_from_rate() {
+        let r = Rate::<Money, Mass>::new(Dec!(1.99), EUR, Dec!(3), KILOGRAM);
+        let p = Price::from_rate(&r, 4).unwrap();
+        assert_eq!(p, Price::new(Dec!(0.6633), EUR, 4));
+        assert_eq!(
+            Price::from_rate(&r, 1),
+            Err(MoneyError::InvalidPrecision {
+                currency: EUR,
+                precision: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,430 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_line_item {
+    use moneta::{Dec, Decimal, LineItem, EUR};
+
+    #[test]
+    fn test_line_item() {
+        let item = LineItem::new("tea", Dec!(2.5), Dec!(3.99) * EUR);
+        assert_eq!(item.article(), "tea");
+        assert_eq!(item.quantity(), Dec!(2.5));
+        assert_eq!(item.unit_price(), Dec!(3.99) * EUR);
+        assert_eq!(item.total(), Dec!(9.98) * EUR);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_quantity() {
+        let _i = LineItem::new("tea", Dec!(-1), Dec!(3.99) * EUR);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_price() {
+        let _i = LineItem::new("tea", Dec!(1), Dec!(-3.99) * EUR);
+    }
+}
+
+#[cfg(test)]
+mod test_pricing_rule {
+    use moneta::{Dec, Decimal, Percent, PricingRule, RuleScope, EUR, USD};
+
+    #[test]
+    fn test_scope_and_cap() {
+        let rule = PricingRule::percent_off(Percent::new(Dec!(10)));
+        assert_eq!(rule.scope(), &RuleScope::AllLines);
+        assert_eq!(rule.cap(), None);
+        let rule = rule.with_cap(Dec!(20) * EUR).for_order();
+        assert_eq!(rule.scope(), &RuleScope::Order);
+        assert_eq!(rule.cap(), Some(Dec!(20) * EUR));
+        let rule = PricingRule::buy_x_get_y(2, 1).for_articles(&["a", "b"]);
+        assert_eq!(
+            rule.scope(),
+            &RuleScope::Articles(vec!["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let rule = PricingRule::percent_off(Percent::new(Dec!(10)))
+            .for_order()
+            .with_cap(Dec!(20) * EUR);
+        assert_eq!(rule.to_string(), "10 % off on order, max 20.00 EUR");
+        let rule = PricingRule::amount_off(Dec!(5) * EUR);
+        assert_eq!(rule.to_string(), "5.00 EUR off");
+        let rule = PricingRule::buy_x_get_y(2, 1).for_articles(&["a", "b"]);
+        assert_eq!(rule.to_string(), "buy 2 get 1 free on a, b");
+        let rule = PricingRule::tiered_price(&[(Dec!(10), Dec!(1) * EUR)]);
+        assert_eq!(rule.to_string(), "tiered price");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tiers_not_ascending() {
+        let _r = PricingRule::tiered_price(&[
+            (Dec!(10), Dec!(1.35) * EUR),
+            (Dec!(10), Dec!(1.20) * EUR),
+        ]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tiers_diff_currency() {
+        let _r = PricingRule::tiered_price(&[
+            (Dec!(10), Dec!(1.35) * EUR),
+            (Dec!(50), Dec!(1.20) * USD),
+        ]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_no_tiers() {
+        let _r = PricingRule::tiered_price(&[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_percent() {
+        let _r = PricingRule::percent_off(Percent::new(Dec!(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_amount() {
+        let _r = PricingRule::amount_off(Dec!(0) * EUR);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_buy_x_get_y() {
+        let _r = PricingRule::buy_x_get_y(2, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_buy_x_get_y_for_order() {
+        let _r = PricingRule::buy_x_get_y(2, 1).for_order();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_order_rule_for_articles() {
+        let _r = PricingRule::amount_off(Dec!(5) * EUR)
+            .for_order()
+            .for_articles(&["a"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cap_diff_currency() {
+        let _r =
+            PricingRule::amount_off(Dec!(5) * EUR).with_cap(Dec!(1) * USD);
+    }
+}
+
+#[cfg(test)]
+mod test_pricing_rules {
+    use moneta::{
+        Dec, Decimal, LineItem, Money, MoneyError, Percent, PricingRule,
+        PricingRules, EUR, USD,
+    };
+
+    fn items() -> Vec<LineItem> {
+        vec![
+            LineItem::new("water", Dec!(12), Dec!(1.50) * EUR),
+            LineItem::new("soap", Dec!(7), Dec!(4.99) * EUR),
+            LineItem::new("tea", Dec!(1), Dec!(25) * EUR),
+        ]
+    }
+
+    fn amounts(pricing: &moneta::PricingBreakdown) -> Vec<Money> {
+        pricing
+            .adjustments()
+            .iter()
+            .map(|adj| adj.amount())
+            .collect()
+    }
+
+    #[test]
+    fn test_no_rules() {
+        let pricing = PricingRules::new(EUR).apply(&items()).unwrap();
+        assert_eq!(pricing.subtotal(), Dec!(77.93) * EUR);
+        assert_eq!(pricing.total(), Dec!(77.93) * EUR);
+        assert!(pricing.discount().is_zero());
+        assert!(pricing.adjustments().is_empty());
+        let pricing = PricingRules::new(EUR).apply(&[]).unwrap();
+        assert_eq!(pricing.total(), Money::zero(EUR));
+    }
+
+    #[test]
+    fn test_tiered_price() {
+        let rules = PricingRules::new(EUR).with_rule(
+            PricingRule::tiered_price(&[
+                (Dec!(5), Dec!(1.40) * EUR),
+                (Dec!(10), Dec!(1.35) * EUR),
+                (Dec!(50), Dec!(1.20) * EUR),
+            ])
+            .for_articles(&["water"]),
+        );
+        let prices = [
+            (Dec!(4), Dec!(6.00)),
+            (Dec!(5), Dec!(7.00)),
+            (Dec!(12), Dec!(16.20)),
+            (Dec!(60), Dec!(72.00)),
+        ];
+        for (quantity, net) in prices {
+            let items = [LineItem::new("water", quantity, Dec!(1.50) * EUR)];
+            let pricing = rules.apply(&items).unwrap();
+            assert_eq!(pricing.total(), net * EUR);
+            assert_eq!(pricing.lines()[0].net(), net * EUR);
+        }
+        let pricing = rules.apply(&items()).unwrap();
+        let line = &pricing.lines()[0];
+        assert_eq!(line.unit_price(), Dec!(1.35) * EUR);
+        assert_eq!(line.gross(), Dec!(18) * EUR);
+        assert_eq!(line.net(), Dec!(16.20) * EUR);
+        assert_eq!(amounts(&pricing), [Dec!(-1.80) * EUR]);
+        assert_eq!(pricing.adjustments()[0].rule(), 0);
+        assert_eq!(pricing.adjustments()[0].line(), Some(0));
+        // other articles are not affected
+        assert_eq!(pricing.lines()[1].unit_price(), Dec!(4.99) * EUR);
+    }
+
+    #[test]
+    fn test_buy_x_get_y() {
+        let rules =
+            PricingRules::new(EUR).with_rule(PricingRule::buy_x_get_y(2, 1));
+        let pricing = rules.apply(&items()).unwrap();
+        assert_eq!(amounts(&pricing), [Dec!(-6) * EUR, Dec!(-9.98) * EUR]);
+        assert_eq!(pricing.lines()[2].net(), Dec!(25) * EUR);
+        let items = [LineItem::new("cheese", Dec!(5.5), Dec!(2.40) * EUR)];
+        let pricing = rules.apply(&items).unwrap();
+        assert_eq!(pricing.total(), Dec!(10.80) * EUR);
+    }
+
+    #[test]
+    fn test_line_discounts() {
+        let rules = PricingRules::new(EUR)
+            .with_rule(
+                PricingRule::percent_off(Percent::new(Dec!(10)))
+                    .with_cap(Dec!(1) * EUR),
+            )
+            .with_rule(
+                PricingRule::amount_off(Dec!(5) * EUR)
+                    .for_articles(&["water"]),
+            );
+        let pricing = rules.apply(&items()).unwrap();
+        assert_eq!(amounts(&pricing), [Dec!(-1) * EUR, Dec!(-5) * EUR]);
+        assert_eq!(pricing.lines()[0].net(), Dec!(12) * EUR);
+        let mut line_adjustments = pricing.line_adjustments(0);
+        assert_eq!(line_adjustments.next().unwrap().rule(), 0);
+        assert_eq!(line_adjustments.next().unwrap().rule(), 1);
+        assert!(line_adjustments.next().is_none());
+        assert_eq!(pricing.total(), Dec!(71.93) * EUR);
+        assert_eq!(pricing.discount(), Dec!(-6) * EUR);
+    }
+
+    #[test]
+    fn test_line_cap_across_lines() {
+        let rules = PricingRules::new(EUR).with_rule(
+            PricingRule::percent_off(Percent::new(Dec!(10)))
+                .with_cap(Dec!(20) * EUR),
+        );
+        let items: Vec<LineItem> = (0..5)
+            .map(|_| LineItem::new("book", Dec!(1), Dec!(100) * EUR))
+            .collect();
+        let pricing = rules.apply(&items).unwrap();
+        assert_eq!(amounts(&pricing), [Dec!(-10) * EUR, Dec!(-10) * EUR]);
+        assert_eq!(pricing.discount(), Dec!(-20) * EUR);
+        let rules = PricingRules::new(EUR).with_rule(
+            PricingRule::percent_off(Percent::new(Dec!(10)))
+                .with_cap(Dec!(25) * EUR),
+        );
+        let pricing = rules.apply(&items).unwrap();
+        assert_eq!(
+            amounts(&pricing),
+            [Dec!(-10) * EUR, Dec!(-10) * EUR, Dec!(-5) * EUR]
+        );
+    }
+
+    #[test]
+    fn test_limited_tiered_price() {
+        let rules = PricingRules::new(EUR).with_rule(
+            PricingRule::tiered_price(&[(Dec!(10), Dec!(1) * EUR)])
+                .with_cap(Dec!(3) * EUR),
+        );
+        let items = [LineItem::new("water", Dec!(12), Dec!(1.50) * EUR)];
+        let pricing = rules.apply(&items).unwrap();
+        let line = &pricing.lines()[0];
+        assert_eq!(line.net(), Dec!(15) * EUR);
+        assert_eq!(line.unit_price(), Dec!(1.50) * EUR);
+        // limited reduction not dividing evenly by the quantity
+        let rules = PricingRules::new(EUR).with_rule(
+            PricingRule::tiered_price(&[(Dec!(3), Dec!(9) * EUR)])
+                .with_cap(Dec!(1) * EUR),
+        );
+        let items = [LineItem::new("soap", Dec!(3), Dec!(10) * EUR)];
+        let pricing = rules.apply(&items).unwrap();
+        let line = &pricing.lines()[0];
+        assert_eq!(amounts(&pricing), [Dec!(-1) * EUR]);
+        assert_eq!(line.net(), Dec!(29) * EUR);
+        assert_eq!(line.unit_price(), Dec!(10) * EUR);
+    }
+
+    #[test]
+    fn test_successive_tiered_prices() {
+        let rules = PricingRules::new(EUR)
+            .with_rule(
+                PricingRule::tiered_price(&[(Dec!(10), Dec!(9) * EUR)])
+                    .with_cap(Dec!(5) * EUR),
+            )
+            .with_rule(PricingRule::tiered_price(&[(
+                Dec!(10),
+                Dec!(8) * EUR,
+            )]));
+        let items = [LineItem::new("soap", Dec!(10), Dec!(10) * EUR)];
+        let pricing = rules.apply(&items).unwrap();
+        assert_eq!(amounts(&pricing), [Dec!(-5) * EUR, Dec!(-15) * EUR]);
+        let line = &pricing.lines()[0];
+        assert_eq!(line.net(), Dec!(80) * EUR);
+        assert_eq!(line.unit_price(), Dec!(8) * EUR);
+    }
+
+    #[test]
+    fn test_tiered_price_before_discount() {
+        let rules = PricingRules::new(EUR)
+            .with_rule(PricingRule::tiered_price(&[(
+                Dec!(10),
+                Dec!(9) * EUR,
+            )]))
+            .with_rule(PricingRule::percent_off(Percent::new(Dec!(50))));
+        let items = [LineItem::new("soap", Dec!(10), Dec!(10) * EUR)];
+        let pricing = rules.apply(&items).unwrap();
+        assert_eq!(amounts(&pricing), [Dec!(-10) * EUR, Dec!(-45) * EUR]);
+        assert_eq!(pricing.total(), Dec!(45) * EUR);
+        assert_eq!(pricing.lines()[0].unit_price(), Dec!(9) * EUR);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tiered_price_after_discount() {
+        let _r = PricingRules::new(EUR)
+            .with_rule(PricingRule::percent_off(Percent::new(Dec!(50))))
+            .with_rule(PricingRule::tiered_price(&[(
+                Dec!(10),
+                Dec!(9) * EUR,
+            )]));
+    }
+
+    #[test]
+    fn test_buy_x_get_y_after_discount() {
+        let rules = PricingRules::new(EUR)
+            .with_rule(PricingRule::percent_off(Percent::new(Dec!(20))))
+            .with_rule(PricingRule::buy_x_get_y(2, 1));
+        let items = [LineItem::new("soap", Dec!(3), Dec!(5) * EUR)];
+        let pricing = rules.apply(&items).unwrap();
+        assert_eq!(amounts(&pricing), [Dec!(-3) * EUR, Dec!(-4) * EUR]);
+        assert_eq!(pricing.total(), Dec!(8) * EUR);
+    }
+
+    #[test]
+    fn test_reduction_limited_to_price() {
+        let rules = PricingRules::new(EUR)
+            .with_rule(PricingRule::amount_off(Dec!(20) * EUR));
+        let pricing = rules.apply(&items()).unwrap();
+        assert_eq!(
+            amounts(&pricing),
+            [Dec!(-18) * EUR, Dec!(-20) * EUR, Dec!(-20) * EUR]
+        );
+        assert_eq!(pricing.lines()[0].net(), Money::zero(EUR));
+        let rules = PricingRules::new(EUR)
+            .with_rule(PricingRule::amount_off(Dec!(100) * EUR).for_order());
+        let pricing = rules.apply(&items()).unwrap();
+        assert_eq!(pricing.total(), Money::zero(EUR));
+    }
+
+    #[test]
+    fn test_order_discounts() {
+        let percent = PricingRule::percent_off(Percent::new(Dec!(10)))
+            .for_order()
+            .with_cap(Dec!(20) * EUR);
+        let amount = PricingRule::amount_off(Dec!(10) * EUR).for_order();
+        let rules = PricingRules::new(EUR)
+            .with_rule(percent.clone())
+            .with_rule(amount.clone());
+        let pricing = rules.apply(&items()).unwrap();
+        assert_eq!(amounts(&pricing), [Dec!(-7.79) * EUR, Dec!(-10) * EUR]);
+        assert_eq!(pricing.total(), Dec!(60.14) * EUR);
+        assert_eq!(pricing.order_adjustments().count(), 2);
+        assert!(pricing.adjustments().iter().all(|adj| adj.line().is_none()));
+        // the order of the rules matters
+        let rules =
+            PricingRules::new(EUR).with_rule(amount).with_rule(percent);
+        let pricing = rules.apply(&items()).unwrap();
+        assert_eq!(amounts(&pricing), [Dec!(-10) * EUR, Dec!(-6.79) * EUR]);
+        assert_eq!(pricing.total(), Dec!(61.14) * EUR);
+        // the cap applies
+        let rules = PricingRules::new(EUR).with_rule(
+            PricingRule::percent_off(Percent::new(Dec!(50)))
+                .for_order()
+                .with_cap(Dec!(20) * EUR),
+        );
+        let pricing = rules.apply(&items()).unwrap();
+        assert_eq!(pricing.total(), Dec!(57.93) * EUR);
+    }
+
+    #[test]
+    fn test_invariants() {
+        let rules = PricingRules::new(EUR)
+            .with_rule(PricingRule::tiered_price(&[(
+                Dec!(10),
+                Dec!(1.333) * EUR,
+            )]))
+            .with_rule(PricingRule::buy_x_get_y(3, 1))
+            .with_rule(PricingRule::percent_off(Percent::new(Dec!(3.3))))
+            .with_rule(
+                PricingRule::percent_off(Percent::new(Dec!(7))).for_order(),
+            );
+        let pricing = rules.apply(&items()).unwrap();
+        let sum = pricing
+            .adjustments()
+            .iter()
+            .fold(pricing.subtotal(), |acc, adj| acc + adj.amount());
+        assert_eq!(sum, pricing.total());
+        for (idx, line) in pricing.lines().iter().enumerate() {
+            let sum = pricing
+                .line_adjustments(idx)
+                .fold(line.gross(), |acc, adj| acc + adj.amount());
+            assert_eq!(sum, line.net());
+        }
+        assert_eq!(rules.rules().len(), 4);
+        assert_eq!(rules.currency(), EUR);
+    }
+
+    #[test]
+    fn test_currency_mismatch() {
+        let rules = PricingRules::new(USD);
+        assert_eq!(
+            rules.apply(&items()),
+            Err(MoneyError::CurrencyMismatch {
+                expected: USD,
+                found: EUR
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rule_diff_currency() {
+        let _r = PricingRules::new(USD)
+            .with_rule(PricingRule::amount_off(Dec!(5) * EUR));
+    }
+}
@@ -0,0 +1,132 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_approx_eq {
+    use moneta::{Dec, Decimal, EUR, JPY};
+
+    #[test]
+    fn test_approx_eq() {
+        let m = Dec!(17.25) * EUR;
+        let tolerance = Dec!(0.01) * EUR;
+        assert!(m.approx_eq(&m, tolerance));
+        assert!(m.approx_eq(&(Dec!(17.26) * EUR), tolerance));
+        assert!(m.approx_eq(&(Dec!(17.24) * EUR), tolerance));
+        assert!(!m.approx_eq(&(Dec!(17.27) * EUR), tolerance));
+        assert!(m.approx_eq(&m, Dec!(0) * EUR));
+        assert!(!m.approx_eq(&(Dec!(17.26) * EUR), Dec!(-0.01) * EUR));
+    }
+
+    #[test]
+    fn test_approx_eq_diff_currency() {
+        let m = Dec!(17) * EUR;
+        assert!(!m.approx_eq(&(Dec!(17) * JPY), Dec!(1) * EUR));
+        assert!(!m.approx_eq(&m, Dec!(1) * JPY));
+    }
+}
+
+#[cfg(test)]
+mod test_money_range {
+    use moneta::{Dec, Decimal, MoneyError, MoneyRange, EUR, USD};
+
+    #[test]
+    fn test_new() {
+        let range = MoneyRange::new(Dec!(10) * USD, Dec!(50) * USD);
+        assert_eq!(range.min(), Dec!(10) * USD);
+        assert_eq!(range.max(), Dec!(50) * USD);
+        assert_eq!(range.currency(), USD);
+        let range = MoneyRange::new(Dec!(10) * USD, Dec!(10) * USD);
+        assert!(range.contains(&(Dec!(10) * USD)));
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            MoneyRange::try_new(Dec!(10) * USD, Dec!(50) * EUR),
+            Err(MoneyError::CurrencyMismatch {
+                expected: USD,
+                found: EUR
+            })
+        );
+        let err =
+            MoneyRange::try_new(Dec!(50) * USD, Dec!(10) * USD).unwrap_err();
+        assert_eq!(
+            err,
+            MoneyError::InvalidRange {
+                min: Dec!(50) * USD,
+                max: Dec!(10) * USD
+            }
+        );
+        assert_eq!(err.to_string(), "Invalid range: 50.00 USD > 10.00 USD.");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_invalid_range() {
+        let _r = MoneyRange::new(Dec!(50) * USD, Dec!(10) * USD);
+    }
+
+    #[test]
+    fn test_contains() {
+        let range = MoneyRange::new(Dec!(-5) * EUR, Dec!(5) * EUR);
+        assert!(range.contains(&(Dec!(-5) * EUR)));
+        assert!(range.contains(&(Dec!(0) * EUR)));
+        assert!(range.contains(&(Dec!(5) * EUR)));
+        assert!(!range.contains(&(Dec!(-5.01) * EUR)));
+        assert!(!range.contains(&(Dec!(5.01) * EUR)));
+        assert!(!range.contains(&(Dec!(0) * USD)));
+    }
+
+    #[test]
+    fn test_clamp() {
+        let range = MoneyRange::new(Dec!(10) * EUR, Dec!(50) * EUR);
+        assert_eq!(range.clamp(Dec!(5) * EUR), Ok(Dec!(10) * EUR));
+        assert_eq!(range.clamp(Dec!(25) * EUR), Ok(Dec!(25) * EUR));
+        assert_eq!(range.clamp(Dec!(55) * EUR), Ok(Dec!(50) * EUR));
+        assert_eq!(
+            range.clamp(Dec!(25) * USD),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: USD
+            })
+        );
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let range = MoneyRange::new(Dec!(10) * EUR, Dec!(50) * EUR);
+        let other = MoneyRange::new(Dec!(50) * EUR, Dec!(70) * EUR);
+        assert!(range.overlaps(&other));
+        assert!(other.overlaps(&range));
+        assert_eq!(
+            range.intersection(&other),
+            Some(MoneyRange::new(Dec!(50) * EUR, Dec!(50) * EUR))
+        );
+        let other = MoneyRange::new(Dec!(20) * EUR, Dec!(30) * EUR);
+        assert!(range.overlaps(&other));
+        assert_eq!(range.intersection(&other), Some(other));
+        let other = MoneyRange::new(Dec!(50.01) * EUR, Dec!(70) * EUR);
+        assert!(!range.overlaps(&other));
+        assert_eq!(range.intersection(&other), None);
+        let other = MoneyRange::new(Dec!(10) * USD, Dec!(50) * USD);
+        assert!(!range.overlaps(&other));
+        assert_eq!(range.intersection(&other), None);
+    }
+
+    #[test]
+    fn test_money_clamp() {
+        let min = Dec!(0) * EUR;
+        let max = Dec!(100) * EUR;
+        assert_eq!((Dec!(-1) * EUR).clamp(min, max), Ok(min));
+        assert_eq!((Dec!(1) * EUR).clamp(min, max), Ok(Dec!(1) * EUR));
+        assert_eq!((Dec!(101) * EUR).clamp(min, max), Ok(max));
+        assert!((Dec!(1) * USD).clamp(min, max).is_err());
+        assert!((Dec!(1) * EUR).clamp(max, min).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_line_tax {
+    use moneta::{
+        Dec, Decimal, Money, MoneyError, Percent, Quantity, EUR, USD,
+    };
+
+    #[test]
+    fn test_add_tax() {
+        let net = Dec!(24.99) * EUR;
+        let split = net.add_tax(&[Percent::new(Dec!(19))]);
+        assert_eq!(split.net(), net);
+        assert_eq!(split.tax(), Dec!(4.75) * EUR);
+        assert_eq!(split.gross(), Dec!(29.74) * EUR);
+        assert_eq!(split.currency(), EUR);
+        assert_eq!(split.taxes().len(), 1);
+        assert_eq!(split.taxes()[0].base(), net);
+        assert_eq!(split.taxes()[0].rate(), Percent::new(Dec!(19)));
+    }
+
+    #[test]
+    fn test_add_multiple_taxes() {
+        let net = Dec!(99.99) * USD;
+        let rates = [Percent::new(Dec!(6.25)), Percent::new(Dec!(1.75))];
+        let split = net.add_tax(&rates);
+        assert_eq!(split.taxes()[0].amount(), Dec!(6.25) * USD);
+        assert_eq!(split.taxes()[1].amount(), Dec!(1.75) * USD);
+        assert_eq!(split.tax(), Dec!(8) * USD);
+        assert_eq!(split.gross(), Dec!(107.99) * USD);
+    }
+
+    #[test]
+    fn test_remove_tax() {
+        let gross = Dec!(29.74) * EUR;
+        let split = gross.remove_tax(&[Percent::new(Dec!(19))]).unwrap();
+        assert_eq!(split.net(), Dec!(24.99) * EUR);
+        assert_eq!(split.tax(), Dec!(4.75) * EUR);
+        assert_eq!(split.gross(), gross);
+        let split = gross.remove_tax(&[]).unwrap();
+        assert_eq!(split.net(), gross);
+        assert!(split.tax().is_zero());
+        assert!(split.taxes().is_empty());
+    }
+
+    #[test]
+    fn test_remove_tax_zero_factor() {
+        let rates = [Percent::new(Dec!(-60)), Percent::new(Dec!(-40))];
+        assert_eq!(
+            (Dec!(10) * EUR).remove_tax(&rates),
+            Err(MoneyError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_remove_multiple_taxes() {
+        let rates = [Percent::new(Dec!(6.25)), Percent::new(Dec!(1.75))];
+        for cents in 1..2000_i64 {
+            let gross = Money::new(Decimal::from(cents) / Dec!(100), USD);
+            let split = gross.remove_tax(&rates).unwrap();
+            let taxes: Money =
+                Money::sum_in(USD, split.taxes().iter().map(|t| t.amount()))
+                    .unwrap();
+            assert_eq!(split.net() + taxes, gross);
+            assert_eq!(split.tax(), taxes);
+            for (tax, rate) in split.taxes().iter().zip(rates) {
+                let exact = split.net().amount() * rate.as_fraction();
+                assert!((tax.amount().amount() - exact).abs() < Dec!(0.01));
+            }
+        }
+        // the remaining cent is assigned to the largest remainder
+        let split = (Dec!(1.29) * USD).remove_tax(&rates).unwrap();
+        assert_eq!(split.net(), Dec!(1.19) * USD);
+        assert_eq!(split.taxes()[0].amount(), Dec!(0.08) * USD);
+        assert_eq!(split.taxes()[1].amount(), Dec!(0.02) * USD);
+    }
+}
+
+#[cfg(test)]
+mod test_invoice_tax {
+    use moneta::{
+        Dec, Decimal, MoneyError, Percent, TaxBreakdown, TaxRounding, EUR,
+        USD,
+    };
+
+    #[test]
+    fn test_rounding_per_line_vs_per_invoice() {
+        let vat = [Percent::new(Dec!(19))];
+        let lines = [(Dec!(0.33) * EUR, &vat[..]); 3];
+        let split =
+            TaxBreakdown::from_lines(lines, TaxRounding::PerLine).unwrap();
+        assert_eq!(split.net(), Dec!(0.99) * EUR);
+        assert_eq!(split.tax(), Dec!(0.18) * EUR);
+        assert_eq!(split.gross(), Dec!(1.17) * EUR);
+        let split =
+            TaxBreakdown::from_lines(lines, TaxRounding::PerInvoice).unwrap();
+        assert_eq!(split.net(), Dec!(0.99) * EUR);
+        assert_eq!(split.tax(), Dec!(0.19) * EUR);
+        assert_eq!(split.gross(), Dec!(1.18) * EUR);
+    }
+
+    #[test]
+    fn test_grouped_by_rate() {
+        let std_rate = [Percent::new(Dec!(19))];
+        let reduced_rate = [Percent::new(Dec!(7))];
+        let lines = [
+            (Dec!(10) * EUR, &std_rate[..]),
+            (Dec!(20) * EUR, &reduced_rate[..]),
+            (Dec!(5) * EUR, &[][..]),
+            (Dec!(30) * EUR, &std_rate[..]),
+        ];
+        let split =
+            TaxBreakdown::from_lines(lines, TaxRounding::default()).unwrap();
+        let taxes = split.taxes();
+        assert_eq!(taxes.len(), 2);
+        assert_eq!(taxes[0].rate(), std_rate[0]);
+        assert_eq!(taxes[0].base(), Dec!(40) * EUR);
+        assert_eq!(taxes[0].amount(), Dec!(7.6) * EUR);
+        assert_eq!(taxes[1].rate(), reduced_rate[0]);
+        assert_eq!(taxes[1].base(), Dec!(20) * EUR);
+        assert_eq!(taxes[1].amount(), Dec!(1.4) * EUR);
+        assert_eq!(split.net(), Dec!(65) * EUR);
+        assert_eq!(split.gross(), Dec!(74) * EUR);
+    }
+
+    #[test]
+    fn test_duplicate_rate() {
+        let vat = Percent::new(Dec!(19));
+        let lines = [(Dec!(10) * EUR, &[vat, vat][..])];
+        for rounding in [TaxRounding::PerLine, TaxRounding::PerInvoice] {
+            let split = TaxBreakdown::from_lines(lines, rounding).unwrap();
+            assert_eq!(split.taxes().len(), 1);
+            assert_eq!(split.taxes()[0].base(), Dec!(10) * EUR);
+            assert_eq!(split.tax(), Dec!(1.9) * EUR);
+        }
+    }
+
+    #[test]
+    fn test_invoice_errors() {
+        let vat = [Percent::new(Dec!(19))];
+        assert_eq!(
+            TaxBreakdown::from_lines([], TaxRounding::PerLine),
+            Err(MoneyError::Empty)
+        );
+        let lines = [(Dec!(10) * EUR, &vat[..]), (Dec!(10) * USD, &vat[..])];
+        assert_eq!(
+            TaxBreakdown::from_lines(lines, TaxRounding::PerLine),
+            Err(MoneyError::CurrencyMismatch {
+                expected: EUR,
+                found: USD
+            })
+        );
+    }
+}
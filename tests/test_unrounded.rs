@@ -0,0 +1,137 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2024 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(test)]
+mod test_unrounded_money {
+    use core::cmp::Ordering;
+
+    use moneta::{
+        Dec, Decimal, MoneyError, Percent, RoundingMode, UnroundedMoney, EUR,
+        JPY, USD,
+    };
+
+    #[test]
+    fn test_new() {
+        let m = UnroundedMoney::new(Dec!(17.123456789), EUR);
+        assert_eq!(m.amount(), Dec!(17.123456789));
+        assert_eq!(m.currency(), EUR);
+        assert!(m.is_positive());
+        assert!((-m).is_negative());
+        assert_eq!((-m).abs(), m);
+        assert!(UnroundedMoney::zero(USD).is_zero());
+        let m: UnroundedMoney = (Dec!(3.5) * JPY).into();
+        assert_eq!(m, UnroundedMoney::new(Dec!(4), JPY));
+        assert_eq!(
+            (Dec!(3.5) * EUR).unrounded(),
+            UnroundedMoney::new(Dec!(3.5), EUR)
+        );
+    }
+
+    #[test]
+    fn test_chained_ops() {
+        let unit_price = Dec!(0.5) * EUR;
+        let mut m = unit_price.unrounded() * Dec!(7);
+        assert_eq!(m.amount(), Dec!(3.5));
+        let mut x = m;
+        x /= Dec!(3);
+        x *= Dec!(3);
+        assert_eq!(x.amount(), Dec!(3.500000000000000001));
+        assert_eq!(x.round(), Dec!(3.5) * EUR);
+        m += Dec!(0.01) * EUR;
+        m -= UnroundedMoney::new(Dec!(0.005), EUR);
+        assert_eq!(m.amount(), Dec!(3.505));
+        assert_eq!(m.finalize(RoundingMode::RoundHalfUp), Dec!(3.51) * EUR);
+        assert_eq!(m.finalize(RoundingMode::RoundHalfEven), Dec!(3.5) * EUR);
+        let m = Dec!(2) * (m - UnroundedMoney::new(Dec!(0.005), EUR));
+        assert_eq!(m / UnroundedMoney::new(Dec!(0.5), EUR), Dec!(14));
+        let m = UnroundedMoney::new(Dec!(200), EUR) * Percent::new(Dec!(2.5));
+        assert_eq!(m.amount(), Dec!(5));
+    }
+
+    #[test]
+    fn test_no_accumulated_rounding() {
+        // rounded per request, the fee would be zero
+        assert!((Dec!(0.000012) * USD).is_zero());
+        let per_request = UnroundedMoney::new(Dec!(0.000012), USD);
+        let total = per_request * Dec!(1250000);
+        assert_eq!(total.amount(), Dec!(15));
+        let total = (0..1000).fold(UnroundedMoney::zero(USD), |acc, _| {
+            acc + per_request * Dec!(1250)
+        });
+        assert_eq!(total.round(), Dec!(15) * USD);
+    }
+
+    #[test]
+    fn test_finalize() {
+        let m = UnroundedMoney::new(Dec!(-2.675), EUR);
+        for (mode, res) in [
+            (RoundingMode::Round05Up, Dec!(-2.67)),
+            (RoundingMode::RoundCeiling, Dec!(-2.67)),
+            (RoundingMode::RoundDown, Dec!(-2.67)),
+            (RoundingMode::RoundFloor, Dec!(-2.68)),
+            (RoundingMode::RoundHalfDown, Dec!(-2.67)),
+            (RoundingMode::RoundHalfEven, Dec!(-2.68)),
+            (RoundingMode::RoundHalfUp, Dec!(-2.68)),
+            (RoundingMode::RoundUp, Dec!(-2.68)),
+        ] {
+            assert_eq!(m.finalize(mode), res * EUR, "{mode:?}");
+        }
+        let m = UnroundedMoney::new(Dec!(7.0001), JPY);
+        assert_eq!(m.finalize(RoundingMode::RoundUp), Dec!(8) * JPY);
+    }
+
+    #[test]
+    fn test_cmp() {
+        let x = UnroundedMoney::new(Dec!(1.004), EUR);
+        let y = UnroundedMoney::new(Dec!(1.0041), EUR);
+        assert!(x < y);
+        assert_eq!(x.partial_cmp(&x), Some(Ordering::Equal));
+        assert_eq!(x.partial_cmp(&UnroundedMoney::new(Dec!(2), USD)), None);
+    }
+
+    #[test]
+    fn test_try_ops() {
+        let x = UnroundedMoney::new(Dec!(1.004), EUR);
+        let y = UnroundedMoney::new(Dec!(2.5), USD);
+        assert_eq!(x.try_add(x), Ok(UnroundedMoney::new(Dec!(2.008), EUR)));
+        assert_eq!(x.try_sub(x), Ok(UnroundedMoney::zero(EUR)));
+        let err = Err(MoneyError::CurrencyMismatch {
+            expected: EUR,
+            found: USD,
+        });
+        assert_eq!(x.try_add(y), err);
+        assert_eq!(x.try_sub(y), err);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_diff_currency() {
+        let _m = UnroundedMoney::new(Dec!(1), EUR) + Dec!(1) * USD;
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sub_diff_currency() {
+        let _m = UnroundedMoney::new(Dec!(1), EUR)
+            - UnroundedMoney::new(Dec!(1), USD);
+    }
+
+    #[test]
+    fn test_display() {
+        let m = UnroundedMoney::new(Dec!(-0.000012), USD);
+        assert_eq!(m.to_string(), "-0.000012 USD");
+        assert_eq!(format!("{m:.2}"), "-0.00 USD");
+        assert_eq!(format!("{m:>11.2}"), "  -0.00 USD");
+        assert_eq!(format!("{m:_<11.2}"), "-0.00 USD__");
+        assert_eq!(format!("{m:^+13}"), "-0.000012 USD");
+        let m = UnroundedMoney::new(Dec!(7.125), USD);
+        assert_eq!(format!("{m:^+12}"), " +7.125 USD ");
+        assert_eq!(format!("{m:011.1}"), "00007.1 USD");
+    }
+}